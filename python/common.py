@@ -1,5 +1,18 @@
+import sys
+
 def load_input(file_name: str):
     with open(file_name, 'r') as fin:
         input_lines = [line.strip() for line in fin if len(line) > 0]
     
-    return input_lines
\ No newline at end of file
+    return input_lines
+
+def rust_solutions():
+    """The Rust solutions, which have to have been built into this
+    environment first (see README.md). Exits with an error if they haven't."""
+    try:
+        import aoc_rs
+    except ImportError:
+        sys.exit("The Rust solutions aren't built into this environment; "
+                 "see README.md for how to build them.")
+
+    return aoc_rs
\ No newline at end of file
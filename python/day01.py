@@ -1,7 +1,9 @@
 #!/usr/bin/env python3
 #pyright: strict, reportImplicitRelativeImport=false
 
-from common import load_input
+import sys
+
+from common import load_input, rust_solutions
 
 def part1(input: list[str]):
     position = 50
@@ -59,6 +61,14 @@ def part2(input: list[str]):
 
 def main():
     input = load_input('../input_files/day01.input.txt')
+
+    # `--rust` hands the work to the Rust solution instead.
+    if '--rust' in sys.argv[1:]:
+        rust = rust_solutions()
+        print(rust.solve(1, 1, input))
+        print(rust.solve(1, 2, input))
+        return
+
     print(part1(input))
     print(part2(input))
 
@@ -0,0 +1,142 @@
+//! `cargo bench`: every day's parse and both parts, on the real input when it can be found and
+//! on generated inputs of growing size, so the reports show how each one scales.
+//!
+//! The known hot spots get their own groups where the generated inputs don't already stress
+//! them: day 2's regex runs once per ID, so it's timed against range width, and day 10's
+//! light search visits up to every on/off pattern, so it's timed against lights per machine.
+//! Day 8's all-pairs parse and day 9's brute force part 2 show up in their days' own groups.
+//!
+//! Run one group with e.g. `cargo bench -- day08`.
+
+use std::{hint::black_box, time::Duration};
+
+use aoc::{Day, Part, generate, input};
+use criterion::{
+    BenchmarkGroup, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+    measurement::WallTime,
+};
+
+/// Generated input sizes, as fractions of the day's typical size: from an eighth of it up to
+/// four times it, to see how each day would cope with a bigger input than it's ever given.
+const SCALES: [(usize, usize); 6] = [(1, 8), (1, 4), (1, 2), (1, 1), (2, 1), (4, 1)];
+
+const SEED: u64 = 2025;
+
+/// Time parsing `input`, then each part that can solve it.
+fn bench_input(group: &mut BenchmarkGroup<'_, WallTime>, day: &dyn Day, label: &str, input: &str) {
+    let Ok(parsed) = day.parse(input) else {
+        eprintln!(
+            "Skipping day {:02} on {}: it doesn't parse",
+            day.day(),
+            label
+        );
+        return;
+    };
+
+    group.bench_with_input(BenchmarkId::new("parse", label), input, |b, input| {
+        b.iter(|| day.parse(black_box(input)))
+    });
+
+    for part in Part::BOTH {
+        if parsed.solve(part).is_err() {
+            eprintln!(
+                "Skipping day {:02} part {} on {}: it can't be solved",
+                day.day(),
+                part.number(),
+                label
+            );
+            continue;
+        }
+        group.bench_function(
+            BenchmarkId::new(format!("part{}", part.number()), label),
+            |b| b.iter(|| parsed.solve(black_box(part))),
+        );
+    }
+}
+
+fn real_inputs(c: &mut Criterion) {
+    for &day in aoc::DAYS {
+        let Ok(source) = input::locate(day.day(), None) else {
+            continue;
+        };
+        let Ok(input) = source.read() else {
+            continue;
+        };
+
+        let mut group = c.benchmark_group(format!("day{:02}-real", day.day()));
+        bench_input(&mut group, day, "real", &input);
+        group.finish();
+    }
+}
+
+fn generated_inputs(c: &mut Criterion) {
+    for &day in aoc::DAYS {
+        let Some(typical) = generate::typical_size(day.day()) else {
+            continue;
+        };
+
+        let mut group = c.benchmark_group(format!("day{:02}", day.day()));
+        for (times, over) in SCALES {
+            let size = (typical * times / over).max(1);
+            let input = generate::generate(day.day(), size, SEED).unwrap();
+
+            group.throughput(Throughput::Elements(size as u64));
+            bench_input(&mut group, day, &size.to_string(), &input);
+        }
+        group.finish();
+    }
+}
+
+fn day02_range_width(c: &mut Criterion) {
+    let day = aoc::get_day(2).unwrap();
+    let mut group = c.benchmark_group("day02-range-width");
+
+    for width in [1_000_u64, 10_000, 100_000, 1_000_000] {
+        let parsed = day.parse(&format!("1-{}\n", width)).unwrap();
+
+        group.throughput(Throughput::Elements(width));
+        for part in Part::BOTH {
+            group.bench_function(
+                BenchmarkId::new(format!("part{}", part.number()), width),
+                |b| b.iter(|| parsed.solve(black_box(part))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn day10_lights(c: &mut Criterion) {
+    let day = aoc::get_day(10).unwrap();
+    let mut group = c.benchmark_group("day10-lights");
+
+    // One button per light and every light to turn on, so the search has to go all the way
+    // down before it finds the answer.
+    for lights in [4, 6, 8, 10, 12] {
+        let buttons = (0..lights)
+            .map(|light| format!("({})", light))
+            .collect::<Vec<_>>();
+        let machine = format!(
+            "[{}] {} {{{}}}\n",
+            "#".repeat(lights),
+            buttons.join(" "),
+            vec!["1"; lights].join(",")
+        );
+        let parsed = day.parse(&machine).unwrap();
+
+        group.bench_function(BenchmarkId::new("part1", lights), |b| {
+            b.iter(|| parsed.solve(black_box(Part::One)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // The biggest inputs take up to a few seconds a go, so keep the samples few.
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = real_inputs, generated_inputs, day02_range_width, day10_lights
+}
+criterion_main!(benches);
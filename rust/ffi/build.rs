@@ -0,0 +1,16 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    // Only OUT_DIR is ours to write to. The checked-in copy in include/ is compared against
+    // this one by the tests, which can also bring it up to date.
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Could not generate the C header")
+        .write_to_file(out_dir.join("aoc.h"));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,82 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated from src/lib.rs by cbindgen. Don't edit it by hand: run the aoc-ffi tests with AOC_FFI_UPDATE_HEADER=1. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ How a call went. Whatever it is, the output buffer holds the answer or says what went wrong.
+ */
+typedef enum AocStatus {
+  /*
+   The answer is in the output buffer.
+   */
+  AOC_STATUS_OK = 0,
+  /*
+   There's no solution for that day.
+   */
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  /*
+   The part wasn't 1 or 2.
+   */
+  AOC_STATUS_UNKNOWN_PART = 2,
+  /*
+   A pointer was null where it can't be. If it was the output buffer, there's no message.
+   */
+  AOC_STATUS_NULL_POINTER = 3,
+  /*
+   The input isn't valid UTF-8.
+   */
+  AOC_STATUS_INVALID_UTF8 = 4,
+  /*
+   The input isn't a puzzle input for that day.
+   */
+  AOC_STATUS_PARSE_FAILED = 5,
+  /*
+   The input is fine, but that part couldn't be solved for it.
+   */
+  AOC_STATUS_SOLVE_FAILED = 6,
+  /*
+   The answer doesn't fit in the output buffer. The message says how much room it needs.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 7,
+  /*
+   The solver hit a bug. The input may well be fine.
+   */
+  AOC_STATUS_PANICKED = 8,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Solve one part of one day's puzzle.
+
+ `input` is `input_len` bytes of UTF-8 puzzle input, and may be null if `input_len` is 0.
+ The answer, or a message saying what went wrong, is written to `out_buf` as a
+ NUL-terminated string of at most `out_len` bytes. A message that doesn't fit is cut short;
+ an answer that doesn't fit isn't written at all, and `AOC_STATUS_BUFFER_TOO_SMALL` comes
+ back instead.
+
+ # Safety
+
+ `input` must be readable for `input_len` bytes and `out_buf` writable for `out_len` bytes,
+ and they mustn't overlap.
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         uintptr_t input_len,
+                         char *out_buf,
+                         uintptr_t out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
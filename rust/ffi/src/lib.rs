@@ -0,0 +1,239 @@
+//! The day solvers behind a C ABI, so other tools can call them in-process.
+//!
+//! Every call reports how it went with an [`AocStatus`] and writes either the answer or an
+//! error message into a buffer the caller owns, so nothing needs freeing afterwards and no
+//! panic ever unwinds out into the caller. The header is `include/aoc.h`:
+//!
+//! ```c
+//! char answer[64];
+//! const char *input = "L68\nL30\nR48\n";
+//! if (aoc_solve(1, 1, (const uint8_t *)input, strlen(input), answer, sizeof answer) == AOC_STATUS_OK) {
+//!     printf("%s\n", answer);
+//! }
+//! ```
+
+use std::{ffi::c_char, panic, slice, str};
+
+use aoc::Part;
+
+/// How a call went. Whatever it is, the output buffer holds the answer or says what went wrong.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The answer is in the output buffer.
+    Ok = 0,
+    /// There's no solution for that day.
+    UnknownDay = 1,
+    /// The part wasn't 1 or 2.
+    UnknownPart = 2,
+    /// A pointer was null where it can't be. If it was the output buffer, there's no message.
+    NullPointer = 3,
+    /// The input isn't valid UTF-8.
+    InvalidUtf8 = 4,
+    /// The input isn't a puzzle input for that day.
+    ParseFailed = 5,
+    /// The input is fine, but that part couldn't be solved for it.
+    SolveFailed = 6,
+    /// The answer doesn't fit in the output buffer. The message says how much room it needs.
+    BufferTooSmall = 7,
+    /// The solver hit a bug. The input may well be fine.
+    Panicked = 8,
+}
+
+/// The answer, or an error and what to say about it.
+fn solve(day: u8, part: u8, input: &[u8]) -> (AocStatus, String) {
+    let Some(solver) = aoc::get_day(day) else {
+        return (
+            AocStatus::UnknownDay,
+            format!("No solution for day {}", day),
+        );
+    };
+    let part = match part {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => {
+            return (
+                AocStatus::UnknownPart,
+                format!("There's no part {}, only 1 and 2", part),
+            );
+        }
+    };
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(e) => {
+            return (
+                AocStatus::InvalidUtf8,
+                format!("The input isn't valid UTF-8: {}", e),
+            );
+        }
+    };
+
+    let parsed = match solver.parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => return (AocStatus::ParseFailed, format!("{:#}", e)),
+    };
+    match parsed.solve(part) {
+        Ok(answer) => (AocStatus::Ok, answer),
+        Err(e) => (AocStatus::SolveFailed, format!("{:#}", e)),
+    }
+}
+
+/// Copy `text` into `out` as a NUL-terminated string, cut short at a character boundary
+/// if it doesn't fit. `out` is never empty.
+fn write_out(out: &mut [u8], text: &str) {
+    let mut len = text.len().min(out.len() - 1);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out[len] = 0;
+}
+
+/// Solve one part of one day's puzzle.
+///
+/// `input` is `input_len` bytes of UTF-8 puzzle input, and may be null if `input_len` is 0.
+/// The answer, or a message saying what went wrong, is written to `out_buf` as a
+/// NUL-terminated string of at most `out_len` bytes. A message that doesn't fit is cut short;
+/// an answer that doesn't fit isn't written at all, and `AOC_STATUS_BUFFER_TOO_SMALL` comes
+/// back instead.
+///
+/// # Safety
+///
+/// `input` must be readable for `input_len` bytes and `out_buf` writable for `out_len` bytes,
+/// and they mustn't overlap.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> AocStatus {
+    if out_buf.is_null() || out_len == 0 {
+        return AocStatus::NullPointer;
+    }
+    // SAFETY: the caller promises `out_buf` is good for `out_len` bytes.
+    let out = unsafe { slice::from_raw_parts_mut(out_buf.cast::<u8>(), out_len) };
+
+    let input = match (input.is_null(), input_len) {
+        (_, 0) => &[][..],
+        (true, _) => {
+            write_out(out, "The input is null but isn't empty");
+            return AocStatus::NullPointer;
+        }
+        // SAFETY: the caller promises `input` is good for `input_len` bytes.
+        (false, _) => unsafe { slice::from_raw_parts(input, input_len) },
+    };
+
+    let (status, text) = panic::catch_unwind(|| solve(day, part, input)).unwrap_or_else(|_| {
+        (
+            AocStatus::Panicked,
+            format!("Day {} part {} panicked; that's a bug", day, part),
+        )
+    });
+
+    if status == AocStatus::Ok && text.len() >= out_len {
+        write_out(
+            out,
+            &format!(
+                "The answer needs {} bytes, counting the NUL",
+                text.len() + 1
+            ),
+        );
+        return AocStatus::BufferTooSmall;
+    }
+    write_out(out, &text);
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, ffi::CStr, fs, path::Path};
+
+    use super::*;
+
+    /// Set to overwrite `include/aoc.h` with the header cbindgen generated for this build.
+    const UPDATE_HEADER_VAR: &str = "AOC_FFI_UPDATE_HEADER";
+
+    fn call(day: u8, part: u8, input: &[u8], out_len: usize) -> (AocStatus, String) {
+        let mut out = vec![0x7f as c_char; out_len];
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                out.len(),
+            )
+        };
+        let text = unsafe { CStr::from_ptr(out.as_ptr()) }
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        (status, text)
+    }
+
+    #[test]
+    fn solves_and_explains_failures() {
+        let example = b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+        assert_eq!(call(1, 2, example, 16), (AocStatus::Ok, "6".to_string()));
+        assert_eq!(call(26, 1, example, 64).0, AocStatus::UnknownDay);
+        assert_eq!(call(1, 3, example, 64).0, AocStatus::UnknownPart);
+        assert_eq!(call(1, 1, b"L\xff\n", 64).0, AocStatus::InvalidUtf8);
+
+        let (status, message) = call(1, 1, b"L68\nX5\n", 256);
+        assert_eq!(status, AocStatus::ParseFailed);
+        assert!(message.contains("line 2"), "{}", message);
+    }
+
+    #[test]
+    fn never_overruns_the_output_buffer() {
+        let (status, message) = call(2, 1, b"11-22\n", 2);
+        assert_eq!((status, message.as_str()), (AocStatus::BufferTooSmall, "T"));
+
+        let (status, message) = call(2, 1, b"11-22\n", 3);
+        assert_eq!((status, message.as_str()), (AocStatus::Ok, "33"));
+
+        assert_eq!(call(1, 1, b"L68\nX5\n", 10).1.len(), 9);
+    }
+
+    #[test]
+    fn null_pointers_are_errors() {
+        let mut out = [0 as c_char; 64];
+        assert_eq!(
+            unsafe { aoc_solve(1, 1, std::ptr::null(), 5, out.as_mut_ptr(), out.len()) },
+            AocStatus::NullPointer
+        );
+        assert_eq!(
+            unsafe { aoc_solve(1, 1, std::ptr::null(), 0, out.as_mut_ptr(), out.len()) },
+            AocStatus::Ok
+        );
+        assert_eq!(
+            unsafe { aoc_solve(1, 1, b"L1".as_ptr(), 2, std::ptr::null_mut(), 64) },
+            AocStatus::NullPointer
+        );
+    }
+
+    #[test]
+    fn header_matches_the_source() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+        let header = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("include")
+            .join("aoc.h");
+        if env::var_os(UPDATE_HEADER_VAR).is_some() {
+            fs::write(&header, generated).unwrap();
+        }
+
+        assert!(
+            fs::read_to_string(&header).unwrap() == generated,
+            "include/aoc.h is out of date; rerun the tests with {}=1 to regenerate it",
+            UPDATE_HEADER_VAR
+        );
+    }
+}
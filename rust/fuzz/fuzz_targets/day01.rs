@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc::{Day, Part, day01::Day01};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Any input may be rejected, but only ever with an error.
+    if let Ok(parsed) = Day01.parse(input) {
+        for part in Part::BOTH {
+            let _ = parsed.solve(part);
+        }
+    }
+});
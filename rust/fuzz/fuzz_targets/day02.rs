@@ -0,0 +1,22 @@
+#![no_main]
+
+use aoc::{Solution, day02::Day02};
+use libfuzzer_sys::fuzz_target;
+
+/// Both parts check every ID in every range, so anything much bigger just times out.
+const MAX_IDS: u64 = 10_000;
+
+fuzz_target!(|input: &str| {
+    // Any input may be rejected, but only ever with an error.
+    let Ok(ranges) = Day02::parse(input) else {
+        return;
+    };
+
+    let ids = ranges.iter().fold(0_u64, |ids, &(first, last)| {
+        ids.saturating_add(last.saturating_sub(first))
+    });
+    if ids <= MAX_IDS {
+        let _ = Day02::part1(&ranges);
+        let _ = Day02::part2(&ranges);
+    }
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use aoc::{Day, Part, day08::Day08};
+use libfuzzer_sys::fuzz_target;
+
+/// Parsing pairs up every junction box with every other, so anything much bigger runs out of memory.
+const MAX_BOXES: usize = 500;
+
+fuzz_target!(|input: &str| {
+    if input.lines().count() > MAX_BOXES {
+        return;
+    }
+
+    // Any input may be rejected, but only ever with an error.
+    if let Ok(parsed) = Day08.parse(input) {
+        for part in Part::BOTH {
+            let _ = parsed.solve(part);
+        }
+    }
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use aoc::{Day, Part, day09::Day09};
+use libfuzzer_sys::fuzz_target;
+
+/// Part 2 checks every tile against every pair of tiles, so anything much bigger just times out.
+const MAX_TILES: usize = 100;
+
+fuzz_target!(|input: &str| {
+    if input.lines().count() > MAX_TILES {
+        return;
+    }
+
+    // Any input may be rejected, but only ever with an error.
+    if let Ok(parsed) = Day09.parse(input) {
+        for part in Part::BOTH {
+            let _ = parsed.solve(part);
+        }
+    }
+});
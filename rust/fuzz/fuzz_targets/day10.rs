@@ -0,0 +1,27 @@
+#![no_main]
+
+use aoc::{Day, Part, day10::Day10};
+use libfuzzer_sys::fuzz_target;
+
+/// Part 1 searches every state a machine's lights can be in, so anything much bigger just times out.
+const MAX_LIGHTS: usize = 16;
+
+fuzz_target!(|input: &str| {
+    // Any input may be rejected, but only ever with an error.
+    let Ok(parsed) = Day10.parse(input) else {
+        return;
+    };
+
+    // Once it parses, the only square brackets in it are the machines' light diagrams.
+    let lights = input
+        .split('[')
+        .skip(1)
+        .map(|diagram| diagram.find(']').unwrap_or(diagram.len()))
+        .max()
+        .unwrap_or(0);
+    if lights <= MAX_LIGHTS {
+        for part in Part::BOTH {
+            let _ = parsed.solve(part);
+        }
+    }
+});
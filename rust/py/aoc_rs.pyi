@@ -0,0 +1,15 @@
+"""The Rust Advent of Code solutions. Input is the whole text or its lines."""
+
+from typing import Any
+
+def solve(day: int, part: int, text: str | list[str]) -> int | str:
+    """Solve one part of a day's puzzle. Raises ValueError for a bad day, part or input."""
+    ...
+
+def parse(day: int, text: str | list[str]) -> Any:
+    """The input as the Rust solution parses it, as plain lists, dicts, numbers and strings."""
+    ...
+
+def days() -> list[int]:
+    """The days there are Rust solutions for."""
+    ...
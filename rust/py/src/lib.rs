@@ -0,0 +1,205 @@
+//! The day solvers as the Python module `aoc_rs`, so the scripts in python/ can hand a day
+//! over to Rust when they want it fast.
+//!
+//! ```python
+//! import aoc_rs
+//! from common import load_input
+//!
+//! lines = load_input('../input_files/day01.input.txt')
+//! aoc_rs.solve(1, 2, lines)    # the answer, as an int
+//! aoc_rs.parse(1, lines)       # the rotations, as [[-1, 68], [-1, 30], ...]
+//! ```
+//!
+//! Input can be the whole text or a list of its lines, as `common.load_input` gives them.
+//! Answers that are numbers come back as `int`, anything else as `str`. Parsed input comes
+//! back as plain lists, dicts, numbers and strings, shaped like the Rust type it was parsed
+//! into. A bad day, part or input raises `ValueError` with the same message the Rust binaries
+//! would give. The GIL is released while a day runs.
+
+use anyhow::{Result, anyhow, bail};
+use aoc::{Day, Part};
+use pyo3::{
+    IntoPyObjectExt,
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyDict, PyInt, PyList},
+};
+use serde_json::Value;
+
+/// Puzzle input, whole or as lines.
+#[derive(FromPyObject)]
+enum Text {
+    Whole(String),
+    Lines(Vec<String>),
+}
+
+impl Text {
+    fn into_string(self) -> String {
+        match self {
+            Text::Whole(text) => text,
+            Text::Lines(lines) => lines.iter().map(|line| format!("{}\n", line)).collect(),
+        }
+    }
+}
+
+fn find_day(day: u8) -> Result<&'static dyn Day> {
+    aoc::get_day(day).ok_or_else(|| anyhow!("No solution for day {}", day))
+}
+
+fn value_error(e: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{:#}", e))
+}
+
+/// A number as a Python `int`, however big; anything else as a `str`.
+fn answer_to_python(py: Python<'_>, answer: String) -> PyResult<Bound<'_, PyAny>> {
+    let digits = answer.strip_prefix('-').unwrap_or(&answer);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        py.get_type::<PyInt>().call1((answer,))
+    } else {
+        answer.into_bound_py_any(py)
+    }
+}
+
+fn value_to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Null => Ok(py.None().into_bound(py)),
+        Value::Bool(b) => b.into_bound_py_any(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_bound_py_any(py),
+            (None, Some(u)) => u.into_bound_py_any(py),
+            (None, None) => n.as_f64().into_bound_py_any(py),
+        },
+        Value::String(s) => s.into_bound_py_any(py),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| value_to_python(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, items)?.into_any())
+        }
+        Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (name, field) in fields {
+                dict.set_item(name, value_to_python(py, field)?)?;
+            }
+            Ok(dict.into_any())
+        }
+    }
+}
+
+/// Solve one part of a day's puzzle.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u8, part: u8, text: Text) -> PyResult<Bound<'_, PyAny>> {
+    let text = text.into_string();
+    let answer = py
+        .detach(|| {
+            let part = match part {
+                1 => Part::One,
+                2 => Part::Two,
+                _ => bail!("There's no part {}, only 1 and 2", part),
+            };
+            find_day(day)?.parse(&text)?.solve(part)
+        })
+        .map_err(value_error)?;
+
+    answer_to_python(py, answer)
+}
+
+/// Parse a day's puzzle input the way the Rust solution does, and give back what it parsed.
+#[pyfunction]
+fn parse(py: Python<'_>, day: u8, text: Text) -> PyResult<Bound<'_, PyAny>> {
+    let text = text.into_string();
+    let parsed = py
+        .detach(|| find_day(day)?.parse(&text)?.to_value())
+        .map_err(value_error)?;
+
+    value_to_python(py, &parsed)
+}
+
+/// The days there are Rust solutions for. (A `Vec<u8>` would reach Python as `bytes`.)
+#[pyfunction]
+fn days() -> Vec<u32> {
+    aoc::DAYS.iter().map(|day| day.day().into()).collect()
+}
+
+#[pymodule]
+fn aoc_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+    fn with_module(f: impl FnOnce(&Bound<'_, PyModule>)) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "aoc_rs").unwrap();
+            aoc_rs(&module).unwrap();
+            f(&module);
+        });
+    }
+
+    #[test]
+    fn solves_from_text_or_lines() {
+        with_module(|m| {
+            let lines = EXAMPLE.lines().collect::<Vec<_>>();
+
+            let answer = m.call_method1("solve", (1, 2, EXAMPLE)).unwrap();
+            assert!(answer.is_instance_of::<PyInt>());
+            assert_eq!(answer.extract::<u64>().unwrap(), 6);
+            assert_eq!(
+                m.call_method1("solve", (1, 1, lines))
+                    .unwrap()
+                    .extract::<u64>()
+                    .unwrap(),
+                3
+            );
+            assert!(
+                m.call_method0("days")
+                    .unwrap()
+                    .extract::<Vec<u32>>()
+                    .unwrap()
+                    .contains(&1)
+            );
+        });
+    }
+
+    #[test]
+    fn parses_into_plain_data() {
+        with_module(|m| {
+            let rotations = m.call_method1("parse", (1, "L68\nR5\n")).unwrap();
+            assert_eq!(
+                rotations.extract::<Vec<Vec<i32>>>().unwrap(),
+                vec![vec![-1, 68], vec![1, 5]]
+            );
+
+            let map = m.call_method1("parse", (4, "@.\n.@\n")).unwrap();
+            assert_eq!(
+                map.extract::<Vec<Vec<String>>>().unwrap(),
+                vec![vec!["Roll", "Floor"], vec!["Floor", "Roll"]]
+            );
+        });
+    }
+
+    #[test]
+    fn bad_requests_raise_value_error() {
+        with_module(|m| {
+            for args in [(26, 1, EXAMPLE), (1, 3, EXAMPLE), (1, 1, "X5\n")] {
+                let err = m.call_method1("solve", args).unwrap_err();
+                Python::attach(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+            }
+
+            let err = m.call_method1("parse", (1, "L68\nX5\n")).unwrap_err();
+            Python::attach(|py| {
+                let message = err.value(py).str().unwrap();
+                assert!(message.to_str().unwrap().contains("line 2"), "{}", message);
+            });
+        });
+    }
+}
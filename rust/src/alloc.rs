@@ -0,0 +1,213 @@
+//! Counting what each phase of a day allocates, for `--profile-alloc`.
+//!
+//! With the `profile-alloc` feature, every binary's heap goes through [`CountingAllocator`],
+//! which keeps running totals that [`count`] reads before and after a phase. Without it the
+//! totals never move, so [`profile`] refuses rather than report a lot of zeroes.
+//!
+//! Each binary installs the allocator itself, with [`count_allocations!`]. The library can't:
+//! the C and Python libraries link it too, and the feature would swap their allocator as well.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::{Result, bail};
+
+use crate::{Day, Part, bench::Phase};
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of everything that passes through it.
+pub struct CountingAllocator;
+
+/// Make [`CountingAllocator`] the global allocator, if the `profile-alloc` feature is on.
+/// Goes at the top level of a binary's `main.rs`.
+#[macro_export]
+macro_rules! count_allocations {
+    () => {
+        #[cfg(feature = "profile-alloc")]
+        #[global_allocator]
+        static GLOBAL: $crate::alloc::CountingAllocator = $crate::alloc::CountingAllocator;
+    };
+}
+
+/// Whether allocations are being counted at all. Anything running has allocated by now, so
+/// the counts only stand still if [`CountingAllocator`] isn't the global allocator.
+pub fn enabled() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+// SAFETY: every call goes straight through to `System`; the counting on the side never
+// touches the memory itself.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    /// Growing a `Vec` or `String` counts as an allocation of its new size, the old one
+    /// being freed at the same time.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// What one phase allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// How far the heap grew above where it was when the phase started, at its highest.
+    pub peak_bytes: usize,
+    /// Calls to allocate or reallocate.
+    pub allocations: usize,
+    /// Everything asked for, whether or not it was freed again before the phase ended.
+    pub bytes_allocated: usize,
+}
+
+/// Run `f`, counting what it allocates. Other threads' allocations are counted too,
+/// so this is only exact when nothing else is running.
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes_allocated: ALLOCATED.load(Ordering::Relaxed) - allocated,
+    };
+    (result, stats)
+}
+
+/// Parse `input` as `day` and solve both parts, counting each phase's allocations. A phase
+/// that fails gives its error instead. The parts are each tried whether or not the other
+/// one fails, but there's nothing to solve if the parse does.
+pub fn profile(day: &dyn Day, input: &str) -> Result<Vec<(Phase, Result<AllocStats>)>> {
+    if !enabled() {
+        bail!("Allocations aren't being counted; rebuild with `--features profile-alloc`");
+    }
+
+    let (parsed, parse) = count(|| day.parse(input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Ok(vec![(Phase::Parse, Err(e))]),
+    };
+
+    let mut phases = vec![(Phase::Parse, Ok(parse))];
+    for (phase, part) in [(Phase::Part1, Part::One), (Phase::Part2, Part::Two)] {
+        let (answer, stats) = count(|| parsed.solve(part));
+        phases.push((phase, answer.map(|_| stats)));
+    }
+
+    Ok(phases)
+}
+
+/// `bytes` in B, KiB, MiB or GiB, whichever reads best.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::count_allocations!();
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[cfg(feature = "profile-alloc")]
+    #[test]
+    fn counts_what_a_phase_allocates() {
+        let (buf, stats) = count(|| {
+            let mut buf = Vec::<u8>::with_capacity(1 << 20);
+            buf.push(1);
+            drop(Vec::<u8>::with_capacity(1 << 10));
+            buf
+        });
+
+        // Other tests allocate on other threads, so these can only be lower bounds.
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes_allocated >= (1 << 20) + (1 << 10));
+        assert!(stats.peak_bytes >= 1 << 20);
+        assert_eq!(buf.len(), 1);
+    }
+
+    #[cfg(feature = "profile-alloc")]
+    #[test]
+    fn profiles_the_phases_that_finish() {
+        // A single junction box is enough to connect for part 1, but part 2 needs two.
+        let day = crate::get_day(8).unwrap();
+        let phases = profile(day, "1,2,3\n").unwrap();
+        let finished = phases
+            .iter()
+            .map(|(_, stats)| stats.is_ok())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases.iter().map(|(phase, _)| *phase).collect::<Vec<_>>(),
+            Phase::ALL
+        );
+        assert_eq!(finished, [true, true, false]);
+
+        let phases = profile(day, "1,2\n").unwrap();
+        assert!(matches!(phases[..], [(Phase::Parse, Err(_))]));
+    }
+
+    #[cfg(not(feature = "profile-alloc"))]
+    #[test]
+    fn profiling_needs_the_feature() {
+        let day = crate::get_day(1).unwrap();
+        assert!(profile(day, "L1\n").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Puzzle answers as whole numbers of any size.
+//!
+//! Answers that add up or multiply together lots of input can outgrow any fixed-width integer
+//! on a big enough input. [`Answer`] does its arithmetic in an `i128` while the result fits,
+//! and moves over to a [`BigInt`] when it doesn't, so the answer is never cut short or wrapped.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+};
+
+use num_bigint::BigInt;
+
+/// A whole number, positive or negative, of any size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Small(i128),
+    /// Only ever holds numbers too big for `Small`, so every number has exactly one form.
+    Big(BigInt),
+}
+
+impl Answer {
+    pub const ZERO: Answer = Answer::Small(0);
+    pub const ONE: Answer = Answer::Small(1);
+
+    fn from_big(big: BigInt) -> Answer {
+        match i128::try_from(&big) {
+            Ok(small) => Answer::Small(small),
+            Err(_) => Answer::Big(big),
+        }
+    }
+
+    fn to_big(&self) -> BigInt {
+        match self {
+            Answer::Small(small) => BigInt::from(*small),
+            Answer::Big(big) => big.clone(),
+        }
+    }
+
+    /// Apply `small` when both sides are small and it doesn't overflow, `big` otherwise.
+    fn combine(
+        self,
+        other: Answer,
+        small: impl FnOnce(i128, i128) -> Option<i128>,
+        big: impl FnOnce(BigInt, BigInt) -> BigInt,
+    ) -> Answer {
+        if let (Answer::Small(a), Answer::Small(b)) = (&self, &other)
+            && let Some(result) = small(*a, *b)
+        {
+            return Answer::Small(result);
+        }
+
+        Answer::from_big(big(self.to_big(), other.to_big()))
+    }
+}
+
+impl Default for Answer {
+    fn default() -> Answer {
+        Answer::ZERO
+    }
+}
+
+macro_rules! answer_from {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Answer {
+                    match i128::try_from(n) {
+                        Ok(small) => Answer::Small(small),
+                        Err(_) => Answer::Big(BigInt::from(n)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(i32, i64, i128, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(big: BigInt) -> Answer {
+        Answer::from_big(big)
+    }
+}
+
+impl Add for Answer {
+    type Output = Answer;
+
+    fn add(self, other: Answer) -> Answer {
+        self.combine(other, i128::checked_add, |a, b| a + b)
+    }
+}
+
+impl AddAssign for Answer {
+    fn add_assign(&mut self, other: Answer) {
+        *self = std::mem::take(self) + other;
+    }
+}
+
+impl Mul for Answer {
+    type Output = Answer;
+
+    fn mul(self, other: Answer) -> Answer {
+        self.combine(other, i128::checked_mul, |a, b| a * b)
+    }
+}
+
+impl MulAssign for Answer {
+    fn mul_assign(&mut self, other: Answer) {
+        *self = std::mem::take(self) * other;
+    }
+}
+
+impl Sum for Answer {
+    fn sum<I: Iterator<Item = Answer>>(iter: I) -> Answer {
+        iter.fold(Answer::ZERO, Add::add)
+    }
+}
+
+impl Product for Answer {
+    fn product<I: Iterator<Item = Answer>>(iter: I) -> Answer {
+        iter.fold(Answer::ONE, Mul::mul)
+    }
+}
+
+impl Ord for Answer {
+    fn cmp(&self, other: &Answer) -> Ordering {
+        match (self, other) {
+            (Answer::Small(a), Answer::Small(b)) => a.cmp(b),
+            _ => self.to_big().cmp(&other.to_big()),
+        }
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Answer) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// So tests can compare answers with plain number literals.
+impl PartialEq<i128> for Answer {
+    fn eq(&self, other: &i128) -> bool {
+        *self == Answer::Small(*other)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Small(small) => write!(f, "{}", small),
+            Answer::Big(big) => write!(f, "{}", big),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_moves_to_bignum_and_back() {
+        let max = Answer::from(i128::MAX);
+        let past_max = max.clone() + Answer::ONE;
+
+        assert!(matches!(past_max, Answer::Big(_)));
+        assert_eq!(
+            past_max.to_string(),
+            "170141183460469231731687303715884105728"
+        );
+        assert!(past_max > max);
+        assert_eq!(past_max + Answer::from(-1), max);
+    }
+
+    #[test]
+    fn sums_and_products_of_u64s() {
+        let product = [u64::MAX; 3]
+            .into_iter()
+            .map(Answer::from)
+            .product::<Answer>();
+        assert_eq!(
+            product.to_string(),
+            (BigInt::from(u64::MAX).pow(3)).to_string()
+        );
+
+        let sum = [u64::MAX, u64::MAX]
+            .into_iter()
+            .map(Answer::from)
+            .sum::<Answer>();
+        assert_eq!(sum, 2 * u64::MAX as i128);
+        assert!(Answer::from(-5) < Answer::from(u128::MAX));
+    }
+}
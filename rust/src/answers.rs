@@ -0,0 +1,201 @@
+//! A local record of confirmed answers, kept in `answers.toml` next to the inputs.
+//!
+//! Answers are stored per day and input hash, so several inputs can be tracked at once:
+//!
+//! ```toml
+//! [day01."0a13f58bb3b2645d81ac469828a2b2fb57feb2d633ff2ff1fcc8f23528c88b56"]
+//! part1 = "3"
+//! part2 = "6"
+//! ```
+//!
+//! A `part1`/`part2` key directly under `[day01]` is also accepted, and applies
+//! to whichever input gets run. That's handy for writing answers in by hand.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use toml::{Table, Value};
+
+use crate::{Part, input};
+
+pub const FILE_NAME: &str = "answers.toml";
+
+/// How an answer compares with what's on record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String },
+    New,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "MATCH"),
+            Verdict::Mismatch { expected } => write!(f, "MISMATCH (expected {})", expected),
+            Verdict::New => write!(f, "NEW"),
+        }
+    }
+}
+
+pub struct AnswerDb {
+    path: PathBuf,
+    table: Table,
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part.number())
+}
+
+fn answer_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl AnswerDb {
+    /// Where the answers live: an existing `answers.toml` in any of the input directories,
+    /// or else a new one in the first input directory that exists.
+    pub fn default_path() -> Option<PathBuf> {
+        let dirs = input::candidate_dirs();
+
+        dirs.iter()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+            .or_else(|| {
+                dirs.iter()
+                    .find(|dir| dir.is_dir())
+                    .map(|dir| dir.join(FILE_NAME))
+            })
+    }
+
+    /// Load the answers at `path`. A missing file is just an empty database.
+    pub fn load(path: &Path) -> Result<AnswerDb> {
+        let table = match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse::<Table>()
+                .with_context(|| format!("{} is not valid TOML", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Table::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(AnswerDb {
+            path: path.to_path_buf(),
+            table,
+        })
+    }
+
+    /// Load the answers at `path`, or at [`AnswerDb::default_path`] if that's `None`.
+    /// Gives `None` if there's nowhere sensible to keep answers at all.
+    pub fn open(path: Option<PathBuf>) -> Result<Option<AnswerDb>> {
+        path.or_else(AnswerDb::default_path)
+            .map(|path| AnswerDb::load(&path))
+            .transpose()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The confirmed answer for this day, part and input, if there is one.
+    pub fn expected(&self, day: u8, part: Part, input_hash: &str) -> Option<String> {
+        let day_table = self.table.get(&day_key(day))?.as_table()?;
+
+        day_table
+            .get(input_hash)
+            .and_then(Value::as_table)
+            .and_then(|answers| answers.get(&part_key(part)))
+            .or_else(|| day_table.get(&part_key(part)))
+            .map(answer_text)
+    }
+
+    pub fn check(&self, day: u8, part: Part, input_hash: &str, answer: &str) -> Verdict {
+        match self.expected(day, part, input_hash) {
+            None => Verdict::New,
+            Some(expected) if expected == answer => Verdict::Match,
+            Some(expected) => Verdict::Mismatch { expected },
+        }
+    }
+
+    /// Confirm `answer` for this day, part and input. Nothing is written until [`AnswerDb::save`].
+    pub fn record(&mut self, day: u8, part: Part, input_hash: &str, answer: &str) {
+        let day_table = self
+            .table
+            .entry(day_key(day))
+            .or_insert_with(|| Value::Table(Table::new()));
+        let Value::Table(day_table) = day_table else {
+            return;
+        };
+        let answers = day_table
+            .entry(input_hash)
+            .or_insert_with(|| Value::Table(Table::new()));
+        let Value::Table(answers) = answers else {
+            return;
+        };
+
+        answers.insert(part_key(part), Value::String(answer.to_string()));
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string(&self.table)?)
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+
+    /// Check a batch of answers, recording any new ones if `record` is set.
+    /// Mismatches are never overwritten; fix those by hand if the old answer was wrong.
+    pub fn verify(
+        &mut self,
+        day: u8,
+        input_hash: &str,
+        answers: &[(Part, &str)],
+        record: bool,
+    ) -> Vec<Verdict> {
+        answers
+            .iter()
+            .map(|&(part, answer)| {
+                let verdict = self.check(day, part, input_hash, answer);
+                if record && verdict == Verdict::New {
+                    self.record(day, part, input_hash, answer);
+                }
+                verdict
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashed_answers_win_over_plain_ones() {
+        let mut db = AnswerDb {
+            path: PathBuf::from(FILE_NAME),
+            table: "[day05]\npart1 = 3\n[day05.abc]\npart1 = \"4\"\n"
+                .parse()
+                .unwrap(),
+        };
+
+        assert_eq!(db.check(5, Part::One, "abc", "4"), Verdict::Match);
+        assert_eq!(db.check(5, Part::One, "def", "3"), Verdict::Match);
+        assert_eq!(db.check(5, Part::Two, "abc", "14"), Verdict::New);
+
+        db.verify(5, "abc", &[(Part::One, "5"), (Part::Two, "14")], true);
+
+        assert_eq!(
+            db.check(5, Part::One, "abc", "5"),
+            Verdict::Mismatch {
+                expected: String::from("4")
+            }
+        );
+        assert_eq!(db.check(5, Part::Two, "abc", "14"), Verdict::Match);
+    }
+}
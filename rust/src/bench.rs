@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{Day, Part};
+
+/// Summary of repeated timings of one phase, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub median_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (ms(samples[mid - 1]) + ms(samples[mid])) / 2.0
+        } else {
+            ms(samples[mid])
+        };
+
+        Stats {
+            median_ms: median,
+            min_ms: ms(samples[0]),
+            max_ms: ms(samples[samples.len() - 1]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayTimings {
+    pub fn phase(&self, phase: Phase) -> &Stats {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Part1 => &self.part1,
+            Phase::Part2 => &self.part2,
+        }
+    }
+}
+
+/// The answers from a measured day, along with how long each phase took. A part that
+/// failed has its error in place of an answer, and was only timed the once, up to failing.
+pub struct Measurement {
+    pub answers: [Result<String>; 2],
+    pub timings: DayTimings,
+}
+
+impl Measurement {
+    /// The measurement, as long as both parts were solved.
+    pub fn solved(self) -> Result<Measurement> {
+        for (part, answer) in Part::BOTH.into_iter().zip(&self.answers) {
+            if let Err(e) = answer {
+                bail!("Part {} failed: {:#}", part.number(), e);
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> Result<T>) -> (Result<T>, Duration) {
+    let now = Instant::now();
+    let result = f();
+
+    (result, now.elapsed())
+}
+
+/// Parse and solve `input` as `day` `runs` times over, timing each phase separately.
+/// The parts are always run against a parse from the same round, so every phase
+/// gets exactly `runs` samples, unless a part fails: that's only tried on the first.
+/// Failing to parse is the only error, since then there's nothing to measure.
+pub fn measure(day: &dyn Day, input: &str, runs: usize) -> Result<Measurement> {
+    if runs == 0 {
+        bail!("Need at least one run to measure anything");
+    }
+
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    let mut answers = [Ok(String::new()), Ok(String::new())];
+
+    for run in 0..runs {
+        let (parsed, parse_time) = time(|| day.parse(input));
+        let parsed = parsed?;
+        samples[0].push(parse_time);
+
+        for (i, part) in Part::BOTH.into_iter().enumerate() {
+            if answers[i].is_err() {
+                continue;
+            }
+
+            let (answer, solve_time) = time(|| parsed.solve(part));
+            if run == 0 {
+                answers[i] = answer;
+            } else if answer.ok().as_ref() != answers[i].as_ref().ok() {
+                bail!(
+                    "Day {:02} gave different answers on different runs",
+                    day.day()
+                );
+            }
+            samples[i + 1].push(solve_time);
+        }
+    }
+
+    let [parse, part1, part2] = samples.map(|mut s| Stats::from_samples(&mut s));
+
+    Ok(Measurement {
+        answers,
+        timings: DayTimings {
+            day: day.day(),
+            runs,
+            parse,
+            part1,
+            part2,
+        },
+    })
+}
+
+/// A whole benchmark run, in the form it gets saved as a baseline.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub days: Vec<DayTimings>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+}
+
+impl Regression {
+    /// How much slower the current run is, as a percentage of the baseline.
+    pub fn slowdown_percent(&self) -> f64 {
+        (self.current_ms / self.baseline_ms - 1.0) * 100.0
+    }
+}
+
+/// Every phase whose median got more than `threshold_percent` slower than in `baseline`.
+/// Days missing from the baseline are ignored.
+pub fn compare(baseline: &Report, current: &Report, threshold_percent: f64) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+
+    for timings in current.days.iter() {
+        let Some(old_timings) = baseline.days.iter().find(|d| d.day == timings.day) else {
+            continue;
+        };
+
+        for phase in Phase::ALL {
+            let baseline_ms = old_timings.phase(phase).median_ms;
+            let current_ms = timings.phase(phase).median_ms;
+
+            if current_ms > baseline_ms * (1.0 + threshold_percent / 100.0) {
+                regressions.push(Regression {
+                    day: timings.day,
+                    phase,
+                    baseline_ms,
+                    current_ms,
+                });
+            }
+        }
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_what_it_can_when_a_part_fails() {
+        // A single junction box is enough to connect for part 1, but part 2 needs two.
+        let day = crate::get_day(8).unwrap();
+        let measurement = measure(day, "1,2,3\n", 3).unwrap();
+
+        let [part1, part2] = &measurement.answers;
+        assert_eq!(part1.as_ref().unwrap(), "1");
+        assert!(part2.is_err());
+        assert!(measurement.solved().is_err());
+
+        assert!(measure(day, "1,2\n", 3).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use aoc::bench::{self, Measurement, Phase, Report};
+use clap::{Args, ValueEnum, value_parser};
+
+use crate::{run::select_days, table::print_table};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    /// Day to benchmark. Every day is benchmarked if omitted.
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// How many times to parse and solve each day.
+    #[arg(short, long, default_value_t = 10, value_parser = value_parser!(u64).range(1..))]
+    runs: u64,
+
+    /// Read the puzzle input from this file, or `-` for stdin.
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Flag any phase that got slower than in this earlier `--format json` report.
+    #[arg(long, value_name = "BASELINE_JSON")]
+    compare: Option<PathBuf>,
+
+    /// How much slower a phase's median may get before it counts as a regression.
+    #[arg(long, default_value_t = 10.0, value_name = "PERCENT")]
+    threshold: f64,
+}
+
+fn print_report(report: &Report) {
+    let rows = report
+        .days
+        .iter()
+        .flat_map(|timings| {
+            Phase::ALL.map(|phase| {
+                let stats = timings.phase(phase);
+                vec![
+                    format!("{:02}", timings.day),
+                    phase.name().to_string(),
+                    format!("{:.3}", stats.median_ms),
+                    format!("{:.3}", stats.min_ms),
+                    format!("{:.3}", stats.max_ms),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    print_table(&["Day", "Phase", "Median ms", "Min ms", "Max ms"], &rows);
+}
+
+pub fn run(args: BenchArgs) -> Result<()> {
+    let baseline: Option<Report> = match &args.compare {
+        Some(path) => Some(
+            serde_json::from_str(&fs::read_to_string(path)?)
+                .with_context(|| format!("Could not read baseline {}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let mut report = Report::default();
+    let mut failures = 0;
+
+    for day in select_days(args.day)? {
+        let measured = aoc::input::read(day.day(), args.input.as_deref())
+            .and_then(|input| bench::measure(day, &input, args.runs as usize))
+            // A part that failed was only timed up to where it gave up, which isn't worth
+            // keeping as a baseline.
+            .and_then(Measurement::solved);
+
+        match measured {
+            Ok(measurement) => report.days.push(measurement.timings),
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", day.day(), e);
+                failures += 1;
+            }
+        }
+    }
+
+    match args.format {
+        Format::Table => print_report(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if let Some(baseline) = baseline {
+        let regressions = bench::compare(&baseline, &report, args.threshold);
+
+        for regression in regressions.iter() {
+            eprintln!(
+                "Regression: day {:02} {} went from {:.3} ms to {:.3} ms (+{:.1}%)",
+                regression.day,
+                regression.phase.name(),
+                regression.baseline_ms,
+                regression.current_ms,
+                regression.slowdown_percent()
+            );
+        }
+
+        if !regressions.is_empty() {
+            bail!(
+                "{} phase(s) regressed by more than {}%",
+                regressions.len(),
+                args.threshold
+            );
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+
+    Ok(())
+}
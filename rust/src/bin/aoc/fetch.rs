@@ -0,0 +1,42 @@
+use anyhow::{Result, bail};
+use aoc::fetch::{self, FetchConfig};
+use clap::{Args, value_parser};
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// Days to download inputs for.
+    #[arg(required = true, value_parser = value_parser!(u8).range(1..=25))]
+    days: Vec<u8>,
+
+    /// Server to download from, instead of adventofcode.com or $AOC_BASE_URL.
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+pub fn run(args: FetchArgs) -> Result<()> {
+    let mut config = FetchConfig::from_env();
+    if let Some(base_url) = args.base_url {
+        config.base_url = base_url;
+    }
+
+    let mut failures = 0;
+
+    for day in args.days {
+        let cached = config.cache_path(day).is_file();
+
+        match fetch::fetch(&config, day) {
+            Ok(path) if cached => println!("Day {:02}: already cached at {}", day, path.display()),
+            Ok(path) => println!("Day {:02}: downloaded to {}", day, path.display()),
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", day, e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} download(s) failed", failures);
+    }
+
+    Ok(())
+}
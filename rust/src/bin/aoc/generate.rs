@@ -0,0 +1,65 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, anyhow};
+use aoc::generate::{self, Guarantees};
+use clap::{Args, builder::RangedU64ValueParser, value_parser};
+
+#[derive(Args)]
+pub struct GenArgs {
+    /// Day to generate an input for.
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// How many lines, ranges, machines etc. to generate. About as many as a real input by default.
+    #[arg(short, long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    size: Option<usize>,
+
+    /// Seed for the random generator; the same seed always gives the same input.
+    /// A random one is picked, and reported, if omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Break what days 7, 9 and 11 assume of their inputs, to see how they cope: give the
+    /// manifold two starts, leave the red tile loop open, or wire a loop into the devices.
+    #[arg(long)]
+    break_guarantees: bool,
+
+    /// Write the input here instead of to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: GenArgs) -> Result<()> {
+    let size = match args.size {
+        Some(size) => size,
+        None => generate::typical_size(args.day)
+            .ok_or_else(|| anyhow!("No input generator for day {}", args.day))?,
+    };
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_nanos() as u64);
+        eprintln!("Using seed {}", seed);
+        seed
+    });
+
+    let guarantees = if args.break_guarantees {
+        Guarantees::Broken
+    } else {
+        Guarantees::Kept
+    };
+    let input = generate::generate_with(args.day, size, seed, guarantees)?;
+
+    match args.output {
+        Some(path) => fs::write(&path, input)
+            .with_context(|| format!("Could not write {}", path.display()))?,
+        None => io::stdout().write_all(input.as_bytes())?,
+    }
+
+    Ok(())
+}
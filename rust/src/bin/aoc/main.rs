@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod fetch;
+mod generate;
+mod new;
+mod parity;
+mod run;
+mod table;
+mod viz;
+
+aoc::count_allocations!();
+
+/// Advent of Code 2025, every day from one binary.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    verbosity: aoc::logging::Verbosity,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every day with --all.
+    Run(run::RunArgs),
+    /// Time parsing and both parts of one day, or every day.
+    Bench(bench::BenchArgs),
+    /// Download puzzle inputs into the input directory, unless they're already there.
+    Fetch(fetch::FetchArgs),
+    /// Set up the source files for a new day and register them.
+    New(new::NewArgs),
+    /// Generate a random puzzle input, e.g. to stress test or benchmark with.
+    Gen(generate::GenArgs),
+    /// Check the Python solutions give the same answers as the Rust ones.
+    Parity(parity::ParityArgs),
+    /// Watch a day's simulation play out, a step at a time.
+    Viz(viz::VizArgs),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    cli.verbosity.init();
+
+    match cli.command {
+        Command::Run(args) => run::run(args),
+        Command::Bench(args) => bench::run(args),
+        Command::Fetch(args) => fetch::run(args),
+        Command::New(args) => new::run(args),
+        Command::Gen(args) => generate::run(args),
+        Command::Parity(args) => parity::run(args),
+        Command::Viz(args) => viz::run(args),
+    }
+}
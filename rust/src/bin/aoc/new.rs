@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use aoc::scaffold;
+use clap::{Args, value_parser};
+
+#[derive(Args)]
+pub struct NewArgs {
+    /// Day to set up.
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Crate to add the day to. Defaults to the one `aoc` was built from.
+    #[arg(long, default_value = env!("CARGO_MANIFEST_DIR"))]
+    crate_dir: PathBuf,
+}
+
+pub fn run(args: NewArgs) -> Result<()> {
+    for path in scaffold::new_day(&args.crate_dir, args.day)? {
+        println!("Created {}", path.display());
+    }
+    println!("Registered day{:02} in src/lib.rs and Cargo.toml", args.day);
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Result, bail};
+use aoc::{
+    Day, Part, generate, input,
+    parity::{self, Comparison, PartResult, PythonSolver},
+};
+use clap::{Args, value_parser};
+
+#[derive(Args)]
+pub struct ParityArgs {
+    /// Only check this day. Every day with a Python solution by default.
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Check this input too. The day's real input is checked by default, if it's there.
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+
+    /// How many random inputs to check each day on.
+    #[arg(short, long, default_value_t = 20)]
+    generated: u64,
+
+    /// How many lines, ranges, machines etc. each random input has.
+    #[arg(short, long, default_value_t = 50)]
+    size: usize,
+
+    /// Seed for the first random input; the rest count up from it.
+    /// A random one is picked, and reported, if omitted.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Python interpreter to run the Python solutions with.
+    #[arg(long, default_value = "python3")]
+    python: String,
+
+    /// Directory holding `common.py` and the `dayNN.py` files. Searched for when omitted.
+    #[arg(long)]
+    python_dir: Option<PathBuf>,
+}
+
+/// A disagreement, shrunk down to the smallest input that still shows it.
+struct Divergence {
+    origin: String,
+    part: Part,
+    minimal_input: String,
+    comparison: Comparison,
+}
+
+pub fn run(args: ParityArgs) -> Result<()> {
+    let python_dir = match &args.python_dir {
+        Some(dir) => dir.clone(),
+        None => parity::find_python_dir()?,
+    };
+    let days = parity::shared_days(&python_dir)
+        .into_iter()
+        .filter(|day| args.day.is_none_or(|only| day.day() == only))
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        bail!(
+            "No day is solved in both Rust and Python in {}",
+            python_dir.display()
+        );
+    }
+
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        eprintln!("Generating from seed {}", seed);
+        seed
+    });
+
+    let mut divergences = 0;
+    for day in days {
+        let mut python = PythonSolver::start(&args.python, &python_dir, day.day())?;
+        let (checked, found) = check_day(day, &mut python, &args, seed)?;
+
+        match &found {
+            None => println!("Day {:02}: all {} inputs agree", day.day(), checked),
+            Some(divergence) => {
+                divergences += 1;
+                report(day.day(), divergence);
+            }
+        }
+    }
+
+    if divergences > 0 {
+        bail!(
+            "{} day(s) gave different answers in Rust and Python",
+            divergences
+        );
+    }
+
+    Ok(())
+}
+
+/// Try every input on one day, stopping at the first that the two disagree on.
+fn check_day(
+    day: &dyn Day,
+    python: &mut PythonSolver,
+    args: &ParityArgs,
+    seed: u64,
+) -> Result<(usize, Option<Divergence>)> {
+    // The real input is only missed quietly if it wasn't asked for by name.
+    let real_input = match args.input.as_deref() {
+        Some(path) => Some(input::locate(day.day(), Some(path))?),
+        None => input::locate(day.day(), None).ok(),
+    };
+
+    let mut inputs = Vec::new();
+    if let Some(source) = real_input {
+        inputs.push((source.to_string(), source.read()?));
+    }
+    for seed in seed..seed + args.generated {
+        let origin = format!("aoc gen {} --size {} --seed {}", day.day(), args.size, seed);
+        inputs.push((origin, generate::generate(day.day(), args.size, seed)?));
+    }
+
+    for (checked, (origin, input)) in inputs.iter().enumerate() {
+        let comparison = parity::compare(day, python, input)?;
+        let Some(&part) = comparison.diverging_parts().first() else {
+            continue;
+        };
+
+        let minimal_input = parity::shrink(input, |candidate| {
+            let comparison = parity::compare(day, python, candidate)?;
+            Ok(comparison.diverging_parts().contains(&part))
+        })?;
+        let comparison = parity::compare(day, python, &minimal_input)?;
+
+        let divergence = Divergence {
+            origin: origin.clone(),
+            part,
+            minimal_input,
+            comparison,
+        };
+        return Ok((checked + 1, Some(divergence)));
+    }
+
+    Ok((inputs.len(), None))
+}
+
+fn report(day: u8, divergence: &Divergence) {
+    let describe = |result: &PartResult| match result {
+        PartResult::Answer(answer) => answer.clone(),
+        PartResult::Error(e) => format!("an error ({})", e),
+    };
+    let i = divergence.part.number() as usize - 1;
+
+    println!(
+        "Day {:02}: part {} diverges on the input from {}",
+        day,
+        divergence.part.number(),
+        divergence.origin
+    );
+    println!(
+        "Shrunk to {} line(s), where Rust gives {} but Python gives {}:",
+        divergence.minimal_input.lines().count(),
+        describe(&divergence.comparison.rust[i]),
+        describe(&divergence.comparison.python[i])
+    );
+    for line in divergence.minimal_input.lines() {
+        println!("    {}", line);
+    }
+}
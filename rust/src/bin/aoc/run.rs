@@ -0,0 +1,350 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+use aoc::{
+    DAYS, Day, Part,
+    alloc::{self, format_bytes},
+    answers::{AnswerDb, Verdict},
+    output::{self, AnswerRecord, Format},
+    stream::{self, LineSource, Streamed},
+};
+use clap::{Args, value_parser};
+
+use crate::table::print_table;
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Day to solve.
+    #[arg(
+        required_unless_present = "all",
+        value_parser = value_parser!(u8).range(1..=25)
+    )]
+    day: Option<u8>,
+
+    /// Solve every day that has a solution.
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+
+    /// Only solve this part.
+    #[arg(short, long, value_parser = value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Read the puzzle input from this file, or `-` for stdin.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Save any new answers as confirmed.
+    #[arg(long)]
+    record: bool,
+
+    /// Confirmed answers to check against. Found next to the inputs by default.
+    #[arg(long, value_name = "ANSWERS_TOML")]
+    answers: Option<PathBuf>,
+
+    /// Solve while reading the input a line at a time, rather than reading it all in first,
+    /// for inputs too big to fit in memory. Only some days can.
+    #[arg(long, conflicts_with = "all")]
+    stream: bool,
+
+    /// Report how much each phase allocates, instead of solving for answers.
+    /// Needs the `profile-alloc` feature.
+    #[arg(long, conflicts_with_all = ["stream", "part", "record"])]
+    profile_alloc: bool,
+}
+
+/// What happened to one part of one day.
+enum Outcome {
+    Skipped,
+    Solved(String, Duration),
+    Failed(anyhow::Error),
+}
+
+/// Everything that came of solving one day's input.
+struct Solved {
+    input_hash: String,
+    parse_time: Duration,
+    parts: [Outcome; 2],
+    /// How each solved part compared with the answer on record.
+    verdicts: [Option<Verdict>; 2],
+}
+
+struct DayReport {
+    day: u8,
+    /// Either the outcome of each part, or why the input couldn't be read and parsed.
+    solved: Result<Solved>,
+}
+
+fn solve_parts(day: &dyn Day, parts: &[Part], input: Option<&Path>) -> Result<Solved> {
+    let input = aoc::input::read(day.day(), input)?;
+
+    let now = Instant::now();
+    let parsed = day.parse(&input)?;
+    let parse_time = now.elapsed();
+
+    let mut outcomes = [Outcome::Skipped, Outcome::Skipped];
+
+    for &part in parts {
+        let now = Instant::now();
+        outcomes[part as usize] = match parsed.solve(part) {
+            Ok(answer) => Outcome::Solved(answer, now.elapsed()),
+            Err(e) => Outcome::Failed(e),
+        };
+    }
+
+    Ok(Solved {
+        input_hash: output::input_hash(&input),
+        parse_time,
+        parts: outcomes,
+        verdicts: [None, None],
+    })
+}
+
+/// [`solve_parts`], but straight from the input as it's read. Parsing happens along the way,
+/// so it all counts as solving time, and parts that can't be streamed are skipped.
+fn stream_parts(day: &dyn Day, parts: &[Part], input: Option<&Path>) -> Result<Solved> {
+    let source = aoc::input::find(day.day(), input)?;
+    let report = stream::solve(day, LineSource::open(day.day(), &source)?)?;
+
+    let mut outcomes = [Outcome::Skipped, Outcome::Skipped];
+
+    for (&part, streamed) in Part::BOTH.iter().zip(report.parts) {
+        if !parts.contains(&part) {
+            continue;
+        }
+
+        outcomes[part as usize] = match streamed {
+            Streamed::Answer(answer) => Outcome::Solved(answer, report.elapsed),
+            Streamed::Failed(e) => Outcome::Failed(e),
+            Streamed::NeedsWholeInput => {
+                eprintln!(
+                    "Day {:02} part {} needs the whole input at once; run without --stream for it",
+                    day.day(),
+                    part.number()
+                );
+                Outcome::Skipped
+            }
+        };
+    }
+
+    Ok(Solved {
+        input_hash: report.input_hash,
+        parse_time: Duration::ZERO,
+        parts: outcomes,
+        verdicts: [None, None],
+    })
+}
+
+fn verify(solved: &mut Solved, day: u8, db: &mut AnswerDb, record: bool) {
+    for (i, part) in Part::BOTH.into_iter().enumerate() {
+        if let Outcome::Solved(answer, _) = &solved.parts[i] {
+            let verdict = db.verify(day, &solved.input_hash, &[(part, answer)], record);
+            solved.verdicts[i] = verdict.into_iter().next();
+        }
+    }
+}
+
+fn print_summary(reports: &[DayReport]) {
+    let cell = |outcome: &Outcome| match outcome {
+        Outcome::Skipped => String::from("-"),
+        Outcome::Solved(answer, _) => answer.clone(),
+        Outcome::Failed(_) => String::from("ERROR"),
+    };
+
+    let status = |verdict: &Option<Verdict>| match verdict {
+        None => String::from("-"),
+        Some(Verdict::Mismatch { .. }) => String::from("MISMATCH"),
+        Some(verdict) => verdict.to_string(),
+    };
+
+    let rows = reports
+        .iter()
+        .map(|r| match &r.solved {
+            Ok(Solved {
+                parts: [part1, part2],
+                verdicts: [verdict1, verdict2],
+                ..
+            }) => vec![
+                format!("{:02}", r.day),
+                cell(part1),
+                status(verdict1),
+                cell(part2),
+                status(verdict2),
+            ],
+            Err(_) => {
+                let mut row = vec![String::from("ERROR"); 5];
+                row[0] = format!("{:02}", r.day);
+                row
+            }
+        })
+        .collect::<Vec<_>>();
+
+    print_table(&["Day", "Part 1", "Status", "Part 2", "Status"], &rows);
+}
+
+fn records(reports: &[DayReport]) -> Vec<AnswerRecord> {
+    let ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+    let mut records = Vec::new();
+
+    for report in reports.iter() {
+        let Ok(solved) = &report.solved else {
+            continue;
+        };
+
+        for (part, outcome) in Part::BOTH.iter().zip(solved.parts.iter()) {
+            if let Outcome::Solved(answer, solve_time) = outcome {
+                records.push(AnswerRecord {
+                    day: report.day,
+                    part: part.number(),
+                    answer: answer.clone(),
+                    parse_ms: ms(&solved.parse_time),
+                    solve_ms: ms(solve_time),
+                    input_hash: solved.input_hash.clone(),
+                });
+            }
+        }
+    }
+
+    records
+}
+
+/// The one day asked for, or every day if none was.
+pub fn select_days(day: Option<u8>) -> Result<Vec<&'static dyn Day>> {
+    match day {
+        Some(n) => match aoc::get_day(n) {
+            Some(day) => Ok(vec![day]),
+            None => bail!("No solution for day {}", n),
+        },
+        None => Ok(DAYS.to_vec()),
+    }
+}
+
+/// `--profile-alloc`: what each phase of each day allocates, as one table.
+fn profile_days(args: &RunArgs) -> Result<()> {
+    let mut rows = Vec::new();
+    let mut failures = 0;
+
+    for day in select_days(args.day)? {
+        let profile = aoc::input::read(day.day(), args.input.as_deref())
+            .and_then(|input| alloc::profile(day, &input));
+
+        let phases = match profile {
+            Ok(phases) => phases,
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", day.day(), e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        // Whatever did finish still gets its row.
+        for (phase, stats) in phases {
+            match stats {
+                Ok(stats) => rows.push(vec![
+                    format!("{:02}", day.day()),
+                    phase.name().to_string(),
+                    format_bytes(stats.peak_bytes),
+                    stats.allocations.to_string(),
+                    format_bytes(stats.bytes_allocated),
+                ]),
+                Err(e) => {
+                    eprintln!("Day {:02} {}: {:#}", day.day(), phase.name(), e);
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if !rows.is_empty() {
+        print_table(
+            &["Day", "Phase", "Peak heap", "Allocations", "Allocated"],
+            &rows,
+        );
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) or part(s) failed", failures);
+    }
+
+    Ok(())
+}
+
+pub fn run(args: RunArgs) -> Result<()> {
+    if args.profile_alloc {
+        return profile_days(&args);
+    }
+
+    let parts = match args.part {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let mut answer_db = AnswerDb::open(args.answers)?;
+
+    let mut reports = select_days(args.day)?
+        .into_iter()
+        .map(|day| DayReport {
+            day: day.day(),
+            solved: if args.stream {
+                stream_parts(day, &parts, args.input.as_deref())
+            } else {
+                solve_parts(day, &parts, args.input.as_deref())
+            },
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(db) = answer_db.as_mut() {
+        for report in reports.iter_mut() {
+            if let Ok(solved) = &mut report.solved {
+                verify(solved, report.day, db, args.record);
+            }
+        }
+        if args.record {
+            db.save()?;
+        }
+    }
+
+    match args.format {
+        Format::Text => print_summary(&reports),
+        format => output::print_records(&records(&reports), format)?,
+    }
+
+    let mut failures = 0;
+    for report in reports.iter() {
+        match &report.solved {
+            Ok(solved) => {
+                for (i, outcome) in solved.parts.iter().enumerate() {
+                    if let Outcome::Failed(e) = outcome {
+                        eprintln!("Day {:02} part {}: {:#}", report.day, i + 1, e);
+                        failures += 1;
+                    }
+                    if let Some(Verdict::Mismatch { expected }) = &solved.verdicts[i] {
+                        eprintln!(
+                            "Day {:02} part {}: answer doesn't match the one on record (expected {})",
+                            report.day,
+                            i + 1,
+                            expected
+                        );
+                        failures += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", report.day, e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) or part(s) failed", failures);
+    }
+
+    Ok(())
+}
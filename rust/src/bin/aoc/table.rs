@@ -0,0 +1,33 @@
+/// Print rows as an aligned plain-text table. The first column is left-aligned
+/// and the rest are right-aligned, since they're mostly numbers.
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let print_row = |row: &[&str]| {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(i, (cell, &width))| match i {
+                0 => format!("{:<width$}", cell),
+                _ => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line);
+    };
+
+    print_row(header);
+    println!(
+        "{}",
+        "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+    );
+    for row in rows.iter() {
+        print_row(&row.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+}
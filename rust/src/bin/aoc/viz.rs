@@ -0,0 +1,228 @@
+use std::{
+    io::{self, IsTerminal},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow, bail};
+use aoc::{
+    Parsed, input,
+    viz::{Animation, Frame, Tone},
+};
+use clap::{Args, value_parser};
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+};
+
+#[derive(Args)]
+pub struct VizArgs {
+    /// Day to watch. Days 1, 4, 7 and 8 have something to see.
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Read the puzzle input from this file, or `-` for stdin.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Steps per second to play at, to begin with.
+    #[arg(long, default_value_t = 10.0)]
+    speed: f64,
+}
+
+/// How often to redraw and check for keys while playing.
+const TICK: Duration = Duration::from_millis(30);
+
+/// The most steps to take between redraws, so a fast speed on a slow day stays responsive.
+const MAX_STEPS_PER_TICK: usize = 10_000;
+
+const GROUP_COLOURS: [Color; 10] = [
+    Color::Red,
+    Color::Green,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+pub fn run(args: VizArgs) -> Result<()> {
+    let day = aoc::get_day(args.day).ok_or_else(|| anyhow!("No solution for day {}", args.day))?;
+    let input = input::read(args.day, args.input.as_deref())?;
+    let parsed = day.parse(&input)?;
+    let Some(animation) = parsed.animate() else {
+        bail!(
+            "There's nothing to watch for day {:02}; try day 1, 4, 7 or 8",
+            args.day
+        );
+    };
+    if !io::stdout().is_terminal() {
+        bail!("aoc viz needs to run in a terminal");
+    }
+
+    let mut player = Player {
+        day: args.day,
+        parsed: parsed.as_ref(),
+        animation,
+        steps: 0,
+        finished: false,
+        playing: true,
+        speed: args.speed.max(Player::MIN_SPEED),
+    };
+
+    let mut terminal = ratatui::try_init()?;
+    let result = player.play(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+
+/// An animation and how it's being played back.
+struct Player<'a> {
+    day: u8,
+    /// Kept around to start the animation over from.
+    parsed: &'a dyn Parsed,
+    animation: Box<dyn Animation + 'a>,
+    steps: usize,
+    finished: bool,
+    playing: bool,
+    /// Steps per second.
+    speed: f64,
+}
+
+impl Player<'_> {
+    const MIN_SPEED: f64 = 0.25;
+    const MAX_SPEED: f64 = 100_000.0;
+
+    fn play(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut last_tick = Instant::now();
+        // Steps that are due but not yet taken, so slow speeds still add up to a step.
+        let mut due = 0.0;
+
+        loop {
+            let frame = self.animation.frame();
+            terminal.draw(|screen| self.draw(screen, &frame))?;
+
+            if event::poll(TICK)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => self.playing = !self.playing && !self.finished,
+                    KeyCode::Right | KeyCode::Char('n') | KeyCode::Char('.') => {
+                        self.playing = false;
+                        self.advance(1);
+                    }
+                    KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => {
+                        self.speed = (self.speed * 2.0).min(Self::MAX_SPEED);
+                    }
+                    KeyCode::Down | KeyCode::Char('-') => {
+                        self.speed = (self.speed / 2.0).max(Self::MIN_SPEED);
+                    }
+                    KeyCode::Char('r') => self.restart(),
+                    _ => {}
+                }
+            }
+
+            let now = Instant::now();
+            if self.playing {
+                due += (now - last_tick).as_secs_f64() * self.speed;
+                let steps = due.floor();
+                due -= steps;
+                self.advance((steps as usize).min(MAX_STEPS_PER_TICK));
+            } else {
+                due = 0.0;
+            }
+            last_tick = now;
+        }
+    }
+
+    fn advance(&mut self, steps: usize) {
+        for _ in 0..steps {
+            if !self.animation.step() {
+                self.finished = true;
+                self.playing = false;
+                return;
+            }
+            self.steps += 1;
+        }
+    }
+
+    fn restart(&mut self) {
+        if let Some(animation) = self.parsed.animate() {
+            self.animation = animation;
+            self.steps = 0;
+            self.finished = false;
+        }
+    }
+
+    fn draw(&self, screen: &mut ratatui::Frame, frame: &Frame) {
+        let [picture_area, caption_area, help_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .areas(screen.area());
+
+        let picture = frame.picture.rows().map(picture_line).collect::<Vec<_>>();
+        let title = format!(" Day {:02}, step {} ", self.day, self.steps);
+        screen.render_widget(
+            Paragraph::new(picture).block(Block::bordered().title(title)),
+            picture_area,
+        );
+
+        screen.render_widget(
+            Paragraph::new(frame.caption.as_str()).wrap(Wrap { trim: true }),
+            caption_area,
+        );
+
+        let state = if self.finished {
+            "Finished".to_string()
+        } else if self.playing {
+            format!("Playing at {} steps/s", self.speed)
+        } else {
+            format!("Paused at {} steps/s", self.speed)
+        };
+        let help = format!(
+            "{} | space: play/pause | right: step | +/-: speed | r: restart | q: quit",
+            state
+        );
+        screen.render_widget(
+            Paragraph::new(help).style(Style::new().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+}
+
+/// One row of a picture, with runs of the same tone drawn as one span.
+fn picture_line(row: &[(char, Tone)]) -> Line<'static> {
+    let spans = row
+        .chunk_by(|a, b| a.1 == b.1)
+        .map(|run| {
+            Span::styled(
+                run.iter().map(|(char, _)| char).collect::<String>(),
+                style(run[0].1),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Line::from(spans)
+}
+
+fn style(tone: Tone) -> Style {
+    match tone {
+        Tone::Faded => Style::new().fg(Color::DarkGray),
+        Tone::Plain => Style::new(),
+        Tone::Active => Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        Tone::Marked => Style::new().fg(Color::Cyan),
+        Tone::Group(group) => Style::new().fg(GROUP_COLOURS[group % GROUP_COLOURS.len()]),
+    }
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day01::Day01;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day01)
+}
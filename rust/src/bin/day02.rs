@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day02::Day02;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day02)
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day03::Day03;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day03)
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day04::Day04;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day04)
+}
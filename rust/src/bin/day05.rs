@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day05::Day05;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day05)
+}
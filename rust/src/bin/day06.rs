@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day06::Day06;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day06)
+}
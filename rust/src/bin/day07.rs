@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day07::Day07;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day07)
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day08::Day08;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day08)
+}
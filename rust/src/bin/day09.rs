@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day09::Day09;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day09)
+}
@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day10::Day10;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day10)
+}
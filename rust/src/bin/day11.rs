@@ -0,0 +1,8 @@
+use anyhow::Result;
+use aoc::day11::Day11;
+
+aoc::count_allocations!();
+
+fn main() -> Result<()> {
+    aoc::run(&Day11)
+}
@@ -1,74 +1,270 @@
-use anyhow::{Result, anyhow};
-use std::{fs, path::absolute};
+use anyhow::Result;
 
-fn part1(lines: Vec<&str>) -> Result<i32> {
-    let mut pos = 50;
-    let mut zero_count = 0;
+use crate::{
+    Solution,
+    answer::Answer,
+    parse::{self, Line, ParseError},
+    grid::Point,
+    stream::{LineSource, Streamed},
+    viz::{Animation, Frame, Tone},
+};
 
-    for line in lines {
-        let (dir, amt) = line.split_at(1);
-        let amt = amt.parse::<i32>()?;
+pub struct Day01;
 
-        let dir = match dir {
-            "L" => Ok(-1),
-            "R" => Ok(1),
-            _ => Err(anyhow!("Dials only go left and right.")),
-        }?;
+/// A single dial instruction: direction (-1 for left, 1 for right) and distance.
+pub type Rotation = (i32, i32);
 
-        pos += amt * dir;
-        pos %= 100;
+fn parse_rotation(line: &Line) -> Result<Rotation, ParseError> {
+    // Split after the first character rather than the first byte,
+    // so stray multi-byte characters don't trip us up.
+    let dir_len = line.text.chars().next().map_or(0, char::len_utf8);
+    let (dir, amt) = line.text.split_at(dir_len);
 
-        if pos == 0 {
-            zero_count += 1;
-        }
+    let dir = match dir {
+        "L" => Ok(-1),
+        "R" => Ok(1),
+        "" => Err(line.error_at_end("a rotation like L68")),
+        _ => Err(line.error(dir, "L or R, since dials only go left and right")),
+    }?;
+    let distance = line.number::<i32>(amt)?;
+    if distance < 0 {
+        return Err(line.error(amt, "a distance of 0 or more"));
     }
 
-    Ok(zero_count)
+    Ok((dir, distance))
+}
+
+fn parse_input(input: &str) -> Result<Vec<Rotation>, ParseError> {
+    parse::lines(1, input)
+        .map(|line| parse_rotation(&line))
+        .collect()
 }
 
-fn part2(lines: Vec<&str>) -> Result<i32> {
-    let mut pos = 50;
-    let mut zero_count = 0;
+/// The dial and how many times it's hit 0, turned one rotation at a time
+/// so the rotations can be streamed straight through it.
+struct Dial {
+    pos: i32,
+    zero_count: Answer,
+}
 
-    for line in lines {
-        let starting_pos = pos;
-        let (dir, amt) = line.split_at(1);
-        let amt = amt.parse::<i32>()?;
+impl Dial {
+    fn new() -> Dial {
+        Dial {
+            pos: 50,
+            zero_count: Answer::ZERO,
+        }
+    }
 
-        let dir = match dir {
-            "L" => Ok(-1),
-            "R" => Ok(1),
-            _ => Err(anyhow!("Dials only go left and right.")),
-        }?;
+    /// Part 1 only counts the dial stopping at 0.
+    fn stop_at_zero(&mut self, (dir, amt): Rotation) {
+        // Whole turns don't move the dial, and leaving them out keeps huge distances from overflowing.
+        self.pos += (amt % 100) * dir;
+        self.pos %= 100;
+
+        if self.pos == 0 {
+            self.zero_count += Answer::ONE;
+        }
+    }
+
+    /// Part 2 counts every time the dial passes 0, too.
+    fn pass_zero(&mut self, (dir, amt): Rotation) {
+        let starting_pos = self.pos;
 
         let full_rotations = amt / 100;
-        zero_count += full_rotations;
+        self.zero_count += full_rotations.into();
 
         let true_amt = (amt % 100) * dir;
 
-        pos += true_amt;
+        self.pos += true_amt;
 
-        if starting_pos != 0 && (pos <= 0 || pos >= 100) {
-            zero_count += 1;
+        if starting_pos != 0 && (self.pos <= 0 || self.pos >= 100) {
+            self.zero_count += Answer::ONE;
         }
 
-        if pos < 0 {
-            pos += 100;
+        if self.pos < 0 {
+            self.pos += 100;
         }
         else {
-            pos %= 100;
+            self.pos %= 100;
+        }
+    }
+}
+
+fn part1(rotations: &[Rotation]) -> Result<Answer> {
+    let mut dial = Dial::new();
+
+    for &rotation in rotations {
+        dial.stop_at_zero(rotation);
+    }
+
+    Ok(dial.zero_count)
+}
+
+fn part2(rotations: &[Rotation]) -> Result<Answer> {
+    let mut dial = Dial::new();
+
+    for &rotation in rotations {
+        dial.pass_zero(rotation);
+    }
+
+    Ok(dial.zero_count)
+}
+
+/// Part 2's dial, turned a rotation at a time.
+struct DialAnimation<'a> {
+    rotations: &'a [Rotation],
+    turned: usize,
+    dial: Dial,
+}
+
+impl Animation for DialAnimation<'_> {
+    fn frame(&self) -> Frame {
+        // Terminal cells are about twice as tall as they are wide, so this comes out round.
+        let (radius_x, radius_y) = (24.0, 11.0);
+        let centre = (radius_x, radius_y);
+        let at = |pos: i32, scale: f64| {
+            let angle = pos as f64 / 100.0 * std::f64::consts::TAU;
+            Point::new(
+                (centre.0 + scale * radius_x * angle.sin()).round() as usize,
+                (centre.1 - scale * radius_y * angle.cos()).round() as usize,
+            )
+        };
+
+        let caption = match self.turned.checked_sub(1).map(|i| self.rotations[i]) {
+            Some((dir, amt)) => format!(
+                "Rotation {} of {}: {}{}, passed 0 {} times so far",
+                self.turned,
+                self.rotations.len(),
+                if dir < 0 { 'L' } else { 'R' },
+                amt,
+                self.dial.zero_count
+            ),
+            None => format!("{} rotations to go", self.rotations.len()),
+        };
+        let mut frame = Frame::blank(2 * radius_x as usize + 1, 2 * radius_y as usize + 1, caption);
+
+        for pos in 0..100 {
+            let (char, tone) = match pos {
+                0 => ('0', Tone::Marked),
+                _ if pos % 10 == 0 => ('+', Tone::Plain),
+                _ => ('.', Tone::Faded),
+            };
+            frame.put(at(pos, 1.0), char, tone);
+        }
+        for step in 1..8 {
+            frame.put(at(self.dial.pos, step as f64 / 10.0), '*', Tone::Plain);
+        }
+        frame.put(at(self.dial.pos, 1.0), '@', Tone::Active);
+
+        let reading = self.dial.pos.to_string();
+        for (i, char) in reading.chars().enumerate() {
+            frame.put(Point::new(centre.0 as usize + i, centre.1 as usize + 3), char, Tone::Plain);
+        }
+
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(&rotation) = self.rotations.get(self.turned) else {
+            return false;
+        };
+        self.dial.pass_zero(rotation);
+        self.turned += 1;
+
+        true
+    }
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Rotation>;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
+
+    fn stream(lines: &mut LineSource) -> Result<(Streamed<Answer>, Streamed<Answer>)> {
+        let (mut part1, mut part2) = (Dial::new(), Dial::new());
+
+        while let Some(line) = lines.next_line()? {
+            let rotation = parse_rotation(&line)?;
+            part1.stop_at_zero(rotation);
+            part2.pass_zero(rotation);
         }
+
+        Ok((
+            Streamed::Answer(part1.zero_count),
+            Streamed::Answer(part2.zero_count),
+        ))
     }
 
-    Ok(zero_count)
+    fn animate(input: &Self::Input) -> Option<Box<dyn Animation + '_>> {
+        Some(Box::new(DialAnimation {
+            rotations: input,
+            turned: 0,
+            dial: Dial::new(),
+        }))
+    }
 }
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string(absolute("../input_files/day01.input.txt")?)?;
-    let lines: Vec<&str> = input.split_terminator('\n').collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
 
-    println!("{}", part1(lines.clone())?);
-    println!("{}", part2(lines)?);
+    #[test]
+    fn part1_example() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part1(&input).unwrap(), 3);
+    }
 
-    Ok(())
+    #[test]
+    fn part2_example() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part2(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn animation_ends_on_part2() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+        let mut animation = Day01::animate(&input).unwrap();
+
+        while animation.step() {}
+        assert_eq!(animation.frame().caption, "Rotation 10 of 10: L82, passed 0 6 times so far");
+    }
+
+    #[test]
+    fn parse_rejects_negative_distance() {
+        let err = parse_input("L68\nR-30\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "-30"));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_direction() {
+        let err = parse_input("L68\nX30\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "X"));
+    }
 }
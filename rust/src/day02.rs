@@ -1,41 +1,101 @@
-use std::{fs, path::absolute};
-
 use anyhow::Result;
 use fancy_regex::Regex;
 
-fn part1(line: String) -> Result<u64> {
-    let re = Regex::new(r"^([0-9]+)\1$")?;
+use crate::{
+    Solution,
+    answer::Answer,
+    parallel,
+    parse::{self, ParseError},
+};
+
+pub struct Day02;
+
+pub type IdRange = (u64, u64);
+
+fn parse_input(input: &str) -> Result<Vec<IdRange>, ParseError> {
+    // The ranges normally all sit on one line, but there's no harm in allowing more.
+    parse::lines(2, input)
+        .flat_map(|line| {
+            line.text.split(',').map(move |x| {
+                let (first, last) = x
+                    .split_once('-')
+                    .ok_or_else(|| line.error(x, "a range of IDs like 11-22"))?;
 
-    let invalid_sum = line
-        .split(',')
-        .map(|x| x.split_once('-').unwrap())
-        .map(|(first, last)| (first.parse::<u64>().unwrap())..(last.parse::<u64>().unwrap() + 1))
-        .flatten()
-        .filter(|x| re.is_match(&x.to_string()).unwrap())
-        .sum::<u64>();
+                Ok((line.number::<u64>(first)?, line.number::<u64>(last)?))
+            })
+        })
+        .collect()
+}
+
+/// Add up every ID in `ranges` that `re` matches.
+/// Each range is independent, so they can be checked in parallel.
+fn sum_invalid(ranges: &[IdRange], re: &Regex) -> Result<Answer> {
+    parallel::try_sum(ranges, |&(first, last)| {
+        let mut invalid_sum = Answer::ZERO;
+
+        for id in first..=last {
+            if re.is_match(&id.to_string())? {
+                invalid_sum += id.into();
+            }
+        }
 
-    Ok(invalid_sum)
+        Ok(invalid_sum)
+    })
 }
 
-fn part2(line: String) -> Result<u64> {
+fn part1(ranges: &[IdRange]) -> Result<Answer> {
+    let re = Regex::new(r"^([0-9]+)\1$")?;
+
+    sum_invalid(ranges, &re)
+}
+
+fn part2(ranges: &[IdRange]) -> Result<Answer> {
     let re = Regex::new(r"^([0-9]+)\1+$")?;
 
-    let invalid_sum = line
-        .split(',')
-        .map(|x| x.split_once('-').unwrap())
-        .map(|(first, last)| (first.parse::<u64>().unwrap())..(last.parse::<u64>().unwrap() + 1))
-        .flatten()
-        .filter(|x| re.is_match(&x.to_string()).unwrap())
-        .sum::<u64>();
+    sum_invalid(ranges, &re)
+}
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<IdRange>;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
 
-    Ok(invalid_sum)
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
 }
 
-fn main() -> Result<()> {
-    let input_line = fs::read_to_string(absolute("../input_files/day02.input.txt")?)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124\n";
+
+    #[test]
+    fn part1_example() {
+        let input = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part1(&input).unwrap(), 1227775554);
+    }
 
-    println!("{}", part1(input_line.clone())?);
-    println!("{}", part2(input_line)?);
+    #[test]
+    fn part2_example() {
+        let input = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part2(&input).unwrap(), 4174379265);
+    }
 
-    Ok(())
+    #[test]
+    fn parse_rejects_range_without_dash() {
+        let err = parse_input("11-22,95\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 7, "95"));
+    }
 }
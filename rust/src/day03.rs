@@ -1,27 +1,60 @@
-use std::{cmp::Ordering, fs, path::absolute};
+use std::cmp::Ordering;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use tracing::{debug, trace};
+
+use crate::{
+    Solution,
+    answer::Answer,
+    parallel,
+    parse::{self, Line, ParseError},
+    stream::{LineSource, Streamed},
+};
+
+pub struct Day03;
+
+/// One bank of batteries, as the joltage digit of each battery in order.
+pub type Bank = Vec<u32>;
+
+fn parse_bank(line: &Line) -> Result<Bank, ParseError> {
+    let bank = line
+        .text
+        .char_indices()
+        .map(|(i, char)| {
+            char.to_digit(10).ok_or_else(|| {
+                line.error(&line.text[i..i + char.len_utf8()], "a battery joltage digit")
+            })
+        })
+        .collect::<Result<Bank, ParseError>>()?;
+
+    // Part 1 turns on two batteries per bank, so that's the least a bank can have.
+    if bank.len() < 2 {
+        return Err(line.error_at_end("at least two batteries in the bank"));
+    }
+
+    Ok(bank)
+}
+
+fn parse_input(input: &str) -> Result<Vec<Bank>, ParseError> {
+    parse::lines(3, input)
+        .map(|line| parse_bank(&line))
+        .collect()
+}
 
 fn compare_elements(a: &(usize, u32), b: &(usize, u32)) -> Ordering {
     // The idea here is sort of a half-reversed sort.
     // We want higher numbers to end up at the front of the result,
     // but we also want matching digits to end up sorted in string order.
-    let a_val: u32 = (a.1 * 1000) + (100 - (a.0 as u32));
-    let b_val = (b.1 * 1000) + (100 - (b.0 as u32));
-
-    b_val.cmp(&a_val)
+    b.1.cmp(&a.1).then(a.0.cmp(&b.0))
 }
 
-fn make_sorted_line(line: &&str) -> Vec<(usize, u32)> {
-    // Given a &str, run it through char_indices to get the elements
-    // we'll operate on for the rest of the script, convert the chars
-    // to u32s, and run them through the sort function.
+fn make_sorted_line(line: &[u32]) -> Vec<(usize, u32)> {
+    // Given a bank, run it through enumerate to get the elements
+    // we'll operate on for the rest of the script, and run them
+    // through the sort function.
     // This gives us prepared input we'll need for both parts.
 
-    let mut sorted_values: Vec<(usize, u32)> = line
-        .char_indices()
-        .map(|(pos, char)| (pos, char.to_digit(10).unwrap()))
-        .collect();
+    let mut sorted_values: Vec<(usize, u32)> = line.iter().copied().enumerate().collect();
 
     sorted_values.sort_by(compare_elements);
 
@@ -29,7 +62,7 @@ fn make_sorted_line(line: &&str) -> Vec<(usize, u32)> {
 }
 
 fn highest_available(
-    sorted_values: &Vec<(usize, u32)>,
+    sorted_values: &[(usize, u32)],
     min_index: Option<usize>,
     max_index: usize,
 ) -> (usize, u32) {
@@ -42,82 +75,145 @@ fn highest_available(
 
     *(sorted_values
         .iter()
-        .filter(|(pos, _char)| min_index.is_none_or(|x| *pos > x) && *pos <= max_index)
-        .nth(0)
+        .find(|(pos, _char)| min_index.is_none_or(|x| *pos > x) && *pos <= max_index)
         .unwrap())
 }
 
-fn part1(lines: &Vec<&str>) -> Result<u32> {
-    let mut result = 0;
+/// The biggest two-digit joltage one bank can make.
+fn best_pair(line: &[u32]) -> u32 {
+    trace!(?line, "Processing bank");
+    let mut this_line_value = 0;
 
-    for line in lines {
-        //println!("Processing line {}", line);
-        let mut this_line_value = 0;
+    let sorted_values = make_sorted_line(line);
 
-        let sorted_values = make_sorted_line(line);
+    let max_value: u32;
+    let max_index: usize;
 
-        let max_value: u32;
-        let max_index: usize;
+    // We want to use the highest value character as our tens place,
+    // but that only works if it's not the last character in the string.
+    if sorted_values[0].0 < line.len() - 1 {
+        (max_index, max_value) = sorted_values[0];
+    } else {
+        (max_index, max_value) = sorted_values[1];
+    }
 
-        // We want to use the highest value character as our tens place,
-        // but that only works if it's not the last character in the string.
-        if sorted_values[0].0 < line.len() - 1 {
-            (max_index, max_value) = sorted_values[0];
-        } else {
-            (max_index, max_value) = sorted_values[1];
-        }
+    this_line_value += max_value * 10;
+
+    let next_highest = sorted_values
+        .iter()
+        .find(|(pos, _char)| *pos > max_index)
+        .unwrap()
+        .1;
+    this_line_value += next_highest;
+
+    debug!(value = this_line_value, "Best pair for this bank");
+    this_line_value
+}
 
-        this_line_value += max_value * 10;
+/// The biggest twelve-digit joltage one bank can make.
+fn best_twelve(line: &[u32]) -> Result<u64> {
+    let mut digit_count = 12;
+    if line.len() < digit_count {
+        bail!("A bank of {} batteries is too small to turn on {}", line.len(), digit_count);
+    }
+
+    trace!(?line, "Processing bank");
+    let mut this_line_value = 0;
+    let mut last_digit_index = None;
 
-        let next_highest = sorted_values
-            .iter()
-            .filter(|(pos, _char)| *pos > max_index)
-            .nth(0)
-            .unwrap()
-            .1;
-        this_line_value += next_highest;
+    let sorted_values = make_sorted_line(line);
 
-        //println!("This line's value: {}", this_line_value);
-        result += this_line_value;
+    while digit_count > 0 {
+        let max_index = line.len() - digit_count;
+        let this_digit;
+        let next_highest = highest_available(&sorted_values, last_digit_index, max_index);
+        (last_digit_index, this_digit) = (Some(next_highest.0), next_highest.1 as u64);
+        digit_count -= 1;
+        // Digits come most significant first, so each one shifts the ones before it along.
+        this_line_value = this_line_value * 10 + this_digit;
     }
 
-    Ok(result)
+    debug!(value = this_line_value, "Best twelve for this bank");
+    Ok(this_line_value)
 }
 
-fn part2(lines: &Vec<&str>) -> Result<u64> {
-    let mut result = 0;
+fn part1(lines: &[Bank]) -> Result<Answer> {
+    parallel::try_sum(lines, |line| Ok(best_pair(line)))
+}
 
-    for line in lines {
-        let mut digit_count = 12;
+fn part2(lines: &[Bank]) -> Result<Answer> {
+    parallel::try_sum(lines, |line| best_twelve(line))
+}
 
-        //println!("Processing line {}", line);
-        let mut this_line_value = 0;
-        let mut last_digit_index = None;
+impl Solution for Day03 {
+    const DAY: u8 = 3;
 
-        let sorted_values = make_sorted_line(line);
+    type Input = Vec<Bank>;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
 
-        while digit_count > 0 {
-            let max_index = line.len() - digit_count;
-            let this_digit;
-            let next_highest = highest_available(&sorted_values, last_digit_index, max_index);
-            (last_digit_index, this_digit) = (Some(next_highest.0), next_highest.1 as u64);
-            digit_count -= 1;
-            this_line_value += this_digit * (10_u64.pow(digit_count as u32));
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
 
-        //println!("This line's value: {}", this_line_value);
-        result += this_line_value;
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
     }
 
-    Ok(result)
+    fn stream(lines: &mut LineSource) -> Result<(Streamed<Answer>, Streamed<Answer>)> {
+        let (mut part1, mut part2) = (Answer::ZERO, Ok(Answer::ZERO));
+
+        while let Some(line) = lines.next_line()? {
+            let bank = parse_bank(&line)?;
+            part1 += best_pair(&bank).into();
+            part2 = part2.and_then(|total| Ok(total + best_twelve(&bank)?.into()));
+        }
+
+        Ok((Streamed::Answer(part1), part2.into()))
+    }
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day03.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part1(&input).unwrap(), 357);
+    }
 
-    println!("{}", part1(&input_lines)?);
-    println!("{}", part2(&input_lines)?);
+    #[test]
+    fn part2_example() {
+        let input = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part2(&input).unwrap(), 3121910778619);
+    }
 
-    Ok(())
+    #[test]
+    fn banks_longer_than_100() {
+        let bank = format!("{}9{}8\n", "1".repeat(150), "1".repeat(150));
+        let input = Day03::parse(&bank).unwrap();
+        assert_eq!(Day03::part1(&input).unwrap(), 98);
+        assert_eq!(Day03::part2(&input).unwrap(), 911111111118);
+    }
+
+    #[test]
+    fn short_banks_are_errors() {
+        let err = parse_input("987\n8\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let input = Day03::parse("987654321\n").unwrap();
+        assert!(Day03::part2(&input).is_err());
+    }
 }
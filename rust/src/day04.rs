@@ -1,23 +1,46 @@
-use itertools::iproduct;
-use std::{collections::HashMap, fs, path::absolute};
+use std::fmt;
 
 use anyhow::Result;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    Solution,
+    grid::{Grid, Point},
+    parse::{self, ParseError},
+    viz::{Animation, Frame, Tone},
+};
+
+pub struct Day04;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Tile {
+    Roll,
+    Floor,
+}
 
-fn prep_map(lines: &Vec<&str>) -> HashMap<(i32, i32), char> {
-    let mut floor_map: HashMap<(i32, i32), char> = HashMap::new();
-
-    for (y, line) in lines.iter().enumerate() {
-        for (x, char) in line.char_indices() {
-            if char == '@' {
-                floor_map.insert((x as i32, y as i32), char);
-            }
-        }
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if *self == Tile::Roll { '@' } else { '.' })
     }
+}
 
-    floor_map
+pub type FloorMap = Grid<Tile>;
+
+fn prep_map(input: &str) -> Result<FloorMap, ParseError> {
+    Grid::parse(
+        4,
+        &parse::lines(4, input).map(|line| line.text).collect_vec(),
+        |char| match char {
+            '@' => Some(Tile::Roll),
+            '.' => Some(Tile::Floor),
+            _ => None,
+        },
+        "a roll of paper (@) or empty floor (.)",
+    )
 }
 
-fn find_removables(floor_map: &HashMap<(i32, i32), char>) -> Vec<(i32, i32, usize)> {
+fn find_removables(floor_map: &FloorMap) -> Vec<Point> {
     // Oh look, another functional programming hellscape!
     // ...Listen, I just find these fun to write. They're definitely
     // not the *best* way to handle everything.
@@ -28,44 +51,42 @@ fn find_removables(floor_map: &HashMap<(i32, i32), char>) -> Vec<(i32, i32, usiz
     // grid are less than 4. So, easy enough to do.
 
     floor_map
-        // Iterate over all the key-value pairs
+        // Iterate over all the tiles, and only bother with the ones holding a roll
         .iter()
-        // Count how many rolls each roll is touching, which is its own (somewhat involved) function.
-        .map(|((x, y), _)| {
+        .filter(|(_, tile)| **tile == Tile::Roll)
+        // Count how many rolls each roll is touching.
+        .map(|(point, _)| {
             (
-                *x,
-                *y,
-                // Get the coordinates of all the surrounding "tiles" by way of the cartesian product.
-                iproduct!((x - 1)..(x + 2), (y - 1)..(y + 2))
-                    // The cartesian product also includes the middle tile itself, so discard that.
-                    .filter(|(offset_x, offset_y)| offset_x != x || offset_y != y)
+                point,
+                // The grid hands us the surrounding tiles, minus any that fall off the edge...
+                floor_map
+                    .neighbours8(point)
                     // Discard any of the surrounding tiles that don't contain another roll
-                    .filter(|(offset_x, offset_y)| floor_map.contains_key(&(*offset_x, *offset_y)))
+                    .filter(|&neighbour| floor_map[neighbour] == Tile::Roll)
                     // And count how many we have left
                     .count(),
             )
         })
-        // So that terrifying map() call has transformed our original grid of characters into a
+        // So that map() call has transformed our original grid of characters into a
         // grid of numbers that indicates how many rolls each tile is touching.
         // That done, we just discard any tile with more than 3 occupied neighbors...
-        .filter(|(_, _, occupied_slots)| *occupied_slots < 4)
+        .filter(|(_, occupied_slots)| *occupied_slots < 4)
+        .map(|(point, _)| point)
         .collect()
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<usize> {
-    let floor_map = prep_map(input_lines);
-
-    let result = find_removables(&floor_map).len();
+fn part1(floor_map: &FloorMap) -> Result<usize> {
+    let result = find_removables(floor_map).len();
 
     Ok(result)
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<usize> {
-    let mut floor_map = prep_map(input_lines);
+fn part2(floor_map: &FloorMap) -> Result<usize> {
+    let mut floor_map = floor_map.clone();
     let mut result = 0;
 
     loop {
-        let these_moves: Vec<(i32, i32, usize)> = find_removables(&floor_map);
+        let these_moves = find_removables(&floor_map);
 
         if these_moves.is_empty() {
             break;
@@ -73,20 +94,131 @@ fn part2(input_lines: &Vec<&str>) -> Result<usize> {
 
         result += these_moves.len();
 
-        for (x, y, _) in these_moves {
-            floor_map.remove(&(x, y));
+        for point in these_moves {
+            floor_map[point] = Tile::Floor;
         }
     }
 
     Ok(result)
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day04.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+/// Part 2's rolls, cleared a wave at a time, with the next wave picked out.
+struct RollAnimation {
+    floor_map: FloorMap,
+    /// The rolls that can go in the next wave.
+    removable: Vec<Point>,
+    waves: usize,
+    removed: usize,
+}
+
+impl Animation for RollAnimation {
+    fn frame(&self) -> Frame {
+        let caption = if self.removable.is_empty() {
+            format!("No more rolls can go: {} removed in {} waves", self.removed, self.waves)
+        } else {
+            format!(
+                "Wave {}: {} rolls can go, {} removed so far",
+                self.waves + 1,
+                self.removable.len(),
+                self.removed
+            )
+        };
+        let mut frame = Frame::blank(self.floor_map.width(), self.floor_map.height(), caption);
+
+        for (point, tile) in self.floor_map.iter() {
+            match tile {
+                Tile::Roll => frame.put(point, '@', Tone::Plain),
+                Tile::Floor => frame.put(point, '.', Tone::Faded),
+            }
+        }
+        for &point in &self.removable {
+            frame.put(point, '@', Tone::Active);
+        }
+
+        frame
+    }
 
-    println!("{}", part1(&input_lines)?);
-    println!("{}", part2(&input_lines)?);
+    fn step(&mut self) -> bool {
+        if self.removable.is_empty() {
+            return false;
+        }
 
-    Ok(())
+        for &point in &self.removable {
+            self.floor_map[point] = Tile::Floor;
+        }
+        self.waves += 1;
+        self.removed += self.removable.len();
+        self.removable = find_removables(&self.floor_map);
+
+        true
+    }
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = FloorMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(prep_map(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        part2(input)
+    }
+
+    fn animate(input: &Self::Input) -> Option<Box<dyn Animation + '_>> {
+        Some(Box::new(RollAnimation {
+            floor_map: input.clone(),
+            removable: find_removables(input),
+            waves: 0,
+            removed: 0,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part1(&input).unwrap(), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part2(&input).unwrap(), 43);
+    }
+
+    #[test]
+    fn animation_ends_on_part2() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        let mut animation = Day04::animate(&input).unwrap();
+
+        assert!(animation.frame().caption.starts_with("Wave 1: 13 rolls can go"));
+        while animation.step() {}
+        assert!(animation.frame().caption.starts_with("No more rolls can go: 43 removed"));
+    }
 }
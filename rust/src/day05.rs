@@ -1,33 +1,58 @@
-use std::{collections::HashSet, fs, path::absolute};
+use std::collections::HashSet;
 
 use anyhow::Result;
+use itertools::Itertools;
+use tracing::{debug, trace};
 
-fn parse_input(input_lines: &Vec<&str>) -> Result<(Vec<(u64, u64)>, Vec<u64>)> {
+use crate::{
+    Solution,
+    answer::Answer,
+    parse::{self, ParseError},
+};
+
+pub struct Day05;
+
+/// The fresh ingredient ID ranges, followed by the available ingredient IDs.
+pub type Inventory = (Vec<(u64, u64)>, Vec<u64>);
+
+fn parse_input(input: &str) -> Result<Inventory, ParseError> {
+    let input_lines = parse::lines(5, input).collect_vec();
     let break_line_no = input_lines
         .iter()
-        .position(|&x| x.is_empty())
-        .expect("No blank line found in input!");
+        .position(|x| x.text.is_empty())
+        .ok_or_else(|| {
+            parse::error_at_end_of_input(5, input, "a blank line after the fresh ID ranges")
+        })?;
     let (range_lines, ingredient_lines) = input_lines.split_at(break_line_no);
 
     let fresh_ranges = range_lines
         .iter()
-        .map(|&x| x.split_once('-').expect("Range line had no dash!"))
-        .map(|(low, high)| (low.parse::<u64>().unwrap(), high.parse::<u64>().unwrap()))
-        .collect();
+        .map(|line| {
+            let (low, high) = line
+                .text
+                .split_once('-')
+                .ok_or_else(|| line.error(line.text, "a range of IDs like 3-5"))?;
+
+            let range = (line.number::<u64>(low)?, line.number::<u64>(high)?);
+            if range.0 > range.1 {
+                return Err(line.error(line.text, "a range that doesn't end before it starts"));
+            }
+
+            Ok(range)
+        })
+        .collect::<Result<_, ParseError>>()?;
 
     // We end up with the blank line on the front of this vector,
     // so we discard the first element when we're building our iterator.
     let ingredients = ingredient_lines[1..]
         .iter()
-        .map(|&x| x.parse::<u64>().unwrap())
-        .collect();
+        .map(|line| line.number::<u64>(line.text))
+        .collect::<Result<_, ParseError>>()?;
 
     Ok((fresh_ranges, ingredients))
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<usize> {
-    let (fresh_ranges, ingredients) = parse_input(input_lines)?;
-
+fn part1((fresh_ranges, ingredients): &Inventory) -> Result<usize> {
     let result = ingredients
         .iter()
         .filter(|&&x| {
@@ -40,16 +65,14 @@ fn part1(input_lines: &Vec<&str>) -> Result<usize> {
     Ok(result)
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<u64> {
-    let (fresh_ranges, _) = parse_input(input_lines)?;
-
+fn part2((fresh_ranges, _): &Inventory) -> Result<Answer> {
     // There is *definitely* a more rust-y way to do what I'm thinking of here,
     // but right now I'm just trying to get it working at all.
 
     let mut keep_looping = true;
 
     let mut these_ranges = HashSet::new();
-    for fresh_range in fresh_ranges {
+    for &fresh_range in fresh_ranges {
         these_ranges.insert(fresh_range);
     }
 
@@ -69,21 +92,17 @@ fn part2(input_lines: &Vec<&str>) -> Result<u64> {
                 .find(|&&x| {
                     x != range
                         && !already_handled.contains(&x)
-                        && ((x.0 <= range.0 && x.1 >= range.0)
-                            || (x.0 <= range.1 && x.1 >= range.1))
+                        // Either range may sit entirely inside the other,
+                        // so check that they overlap at all rather than for a shared endpoint.
+                        && x.0 <= range.1
+                        && x.1 >= range.0
                 })
             {
-                /*
-                println!(
-                    "Overlap between ranges {:?} and {:?}",
-                    range, overlapping_range
-                );
-                 */
                 let new_range = (
                     range.0.min(overlapping_range.0),
                     range.1.max(overlapping_range.1),
                 );
-                //println!("New range is {:?}", new_range);
+                debug!(?range, ?overlapping_range, ?new_range, "Merging overlapping ranges");
                 next_ranges.insert(new_range);
                 already_handled.insert(range);
                 already_handled.insert(*overlapping_range);
@@ -96,22 +115,89 @@ fn part2(input_lines: &Vec<&str>) -> Result<u64> {
         }
 
         these_ranges = next_ranges;
+        trace!(ranges = these_ranges.len(), "Finished a pass over the ranges");
     }
 
-    let result: u64 = these_ranges
+    // A single range can cover every u64 there is, which is one more than a u64 can count.
+    let fresh = these_ranges
         .iter()
-        .map(|(low, high)| *high - *low + 1)
+        .map(|(low, high)| Answer::from(high - low) + Answer::ONE)
         .sum();
 
-    Ok(result)
+    Ok(fresh)
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Inventory;
+    type Answer1 = usize;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day05.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part1(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part2(&input).unwrap(), 14);
+    }
+
+    #[test]
+    fn part2_range_inside_another() {
+        let input = Day05::parse("16-40\n28-37\n\n").unwrap();
+        assert_eq!(Day05::part2(&input).unwrap(), 25);
+    }
 
-    println!("{}", part1(&input_lines)?);
-    println!("{}", part2(&input_lines)?);
+    #[test]
+    fn part2_every_id_is_one_more_than_a_u64() {
+        let input = Day05::parse("0-18446744073709551615\n\n").unwrap();
+        assert_eq!(Day05::part2(&input).unwrap(), 1 << 64);
+    }
 
-    Ok(())
+    #[test]
+    fn parse_rejects_backwards_range() {
+        let err = parse_input("3-5\n10-4\n\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn parse_requires_blank_line() {
+        let err = parse_input("3-5\n10-14\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
 }
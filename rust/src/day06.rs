@@ -1,67 +1,146 @@
-use std::{fs, path::absolute};
-
 use anyhow::{Result, anyhow};
 use itertools::Itertools;
+use serde::Serialize;
 
-fn parse_input(input_lines: &Vec<&str>) -> Vec<Vec<String>> {
-    let column_count = input_lines
-        .first()
-        .expect("No input given?!")
-        .split_whitespace()
-        .collect_vec()
-        .len();
+use crate::{
+    Solution,
+    answer::Answer,
+    parallel,
+    parse::{self, Line, ParseError},
+    stream::{LineSource, Streamed},
+};
+
+pub struct Day06;
+
+/// The worksheet read both ways: whitespace-separated columns for part 1,
+/// and right-to-left digit columns plus operators for part 2.
+#[derive(Serialize)]
+pub struct Worksheet {
+    columns: Vec<Vec<String>>,
+    vertical: (Vec<Vec<u64>>, Vec<String>),
+}
+
+fn parse_input(input_lines: &[Line]) -> Result<Vec<Vec<String>>, ParseError> {
+    let column_count = input_lines[0].text.split_whitespace().collect_vec().len();
+    let operator_line = input_lines.len() - 1;
 
     let mut result = vec![Vec::new(); column_count];
 
-    for line in input_lines {
-        line.split_whitespace()
-            .enumerate()
-            .for_each(|(i, str)| result[i].push(str.into()));
+    for (line_no, line) in input_lines.iter().enumerate() {
+        let mut fields = 0;
+
+        for (i, str) in line.text.split_whitespace().enumerate() {
+            if i >= column_count {
+                return Err(line.error(str, format!("only {} problems per line", column_count)));
+            }
+
+            // Part 1 does its own number parsing, but it's much nicer
+            // to find out about bad fields here, where we know where they are.
+            if line_no == operator_line {
+                if str != "+" && str != "*" {
+                    return Err(line.error(str, "an operator (+ or *)"));
+                }
+            } else {
+                line.number::<u64>(str)?;
+            }
+
+            result[i].push(str.into());
+            fields += 1;
+        }
+
+        if fields < column_count {
+            return Err(line.error_at_end(format!("{} problems per line", column_count)));
+        }
     }
 
-    result
+    Ok(result)
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<u64> {
-    let input_columns = parse_input(input_lines);
-    let mut result: u64 = 0;
+/// Add or multiply `operands` together.
+fn evaluate(operation: &str, operands: impl IntoIterator<Item = u64>) -> Result<Answer> {
+    let operands = operands.into_iter().map(Answer::from);
 
-    for mut column in input_columns {
-        let operation = column.pop().expect("Got an empty input column");
+    match operation {
+        "+" => Ok(operands.sum()),
+        "*" => Ok(operands.product()),
+        _ => Err(anyhow!("Got something that very was not an operator!")),
+    }
+}
+
+fn part1(input_columns: &[Vec<String>]) -> Result<Answer> {
+    // Every problem is worked out on its own, so they can go in parallel.
+    parallel::try_sum(input_columns, |column| {
+        let (operation, operands) = column
+            .split_last()
+            .ok_or_else(|| anyhow!("Got an empty input column"))?;
+
+        let operands = operands
+            .iter()
+            .map(|x| x.parse::<u64>().map_err(|e| anyhow!("Unable to parse operand {}: {}", x, e)))
+            .collect::<Result<Vec<_>>>()?;
+
+        evaluate(operation, operands)
+    })
+}
+
+/// Part 1, a line at a time. Which operator each problem uses isn't known until the last line,
+/// so keep both its sum and its product going until then.
+fn stream_part1(lines: &mut LineSource) -> Result<Answer> {
+    let mut totals: Option<Vec<(Answer, Answer)>> = None;
+    let mut answer = None;
+
+    while let Some(line) = lines.next_line()? {
+        if answer.is_some() {
+            return Err(line.error(line.text, "nothing after the line of operators").into());
+        }
+
+        let fields = line.text.split_whitespace().collect_vec();
+        let totals = totals.get_or_insert_with(|| vec![(Answer::ZERO, Answer::ONE); fields.len()]);
+        if let Some(extra) = fields.get(totals.len()) {
+            return Err(line.error(extra, format!("only {} problems per line", totals.len())).into());
+        }
+        if fields.len() < totals.len() {
+            return Err(line.error_at_end(format!("{} problems per line", totals.len())).into());
+        }
 
-        let operands = column
-            .into_iter()
-            .map(|x| x.parse::<u64>().expect("Unable to parse operand"));
+        if fields.first().is_some_and(|&field| field == "+" || field == "*") {
+            let mut result = Answer::ZERO;
+            for (&field, (sum, product)) in fields.iter().zip(totals.drain(..)) {
+                result += match field {
+                    "+" => sum,
+                    "*" => product,
+                    _ => return Err(line.error(field, "an operator (+ or *)").into()),
+                };
+            }
 
-        if operation == "+" {
-            result += operands.sum::<u64>();
-        } else if operation == "*" {
-            result += operands.product::<u64>()
+            answer = Some(result);
         } else {
-            return Err(anyhow!("Got something that very was not an operator!"));
+            for (&field, (sum, product)) in fields.iter().zip(totals.iter_mut()) {
+                let number = Answer::from(line.number::<u64>(field)?);
+                *sum += number.clone();
+                *product *= number;
+            }
         }
     }
 
-    Ok(result)
+    answer.ok_or_else(|| lines.error_at_end("a line of operators (+ or *)").into())
 }
 
-fn vertical_parse(input_lines: &Vec<&str>) -> (Vec<Vec<u64>>, Vec<String>) {
+fn vertical_parse(input_lines: &[Line]) -> Result<(Vec<Vec<u64>>, Vec<String>), ParseError> {
     // Here's some horrific shenaniganry! A sane man would have written actual loops,
     // but we're stuck with me.
 
     // First thing: let's get a range over every valid index into the input strings.
     // The input strings are always the same length, so this is safe, but I'll do some
     // double-checking later anyways. For laughs.
-    let operands = (0..input_lines
-        .first()
-        .expect("Some input would be nice.")
-        .len())
+    let first_line = input_lines[0];
+    let operands = (0..first_line.text.len())
         .map(|i| {
             // Now, we're iterating over i as every valid index in every line,
             // so to read a single column, we just fetch the i-th character in each line.
-            input_lines
+            let digits = input_lines
                 .iter()
-                .filter_map(|&line| line.chars().nth(i))
+                .filter_map(|line| line.text.chars().nth(i))
                 // It's easier to just get the operators after the fact, so discard any character that isn't
                 // a valid base 10 digit. This also effectively trims the whitespace.
                 .filter_map(|x| match x.is_ascii_digit() {
@@ -69,66 +148,133 @@ fn vertical_parse(input_lines: &Vec<&str>) -> (Vec<Vec<u64>>, Vec<String>) {
                     false => None,
                 })
                 // Join the individual digit strings into the full number.
-                .join("")
+                .join("");
+
+            (i, digits)
         })
         .collect_vec()
         // So we now have a vector of strings containing each of the columns.
         // There will be at least one blank column in between each "group" of columns,
         // so we can use split() to chunk them out.
-        .split(|x| x.is_empty())
+        .split(|(_, x)| x.is_empty())
+        // Trailing blank columns leave an empty group at the end, which isn't a problem at all.
+        .filter(|x| !x.is_empty())
         // We now have an iterator of slices, which is a little awkward to work with,
         // but each element of those slices is a string containing a valid number.
         // We can use a nested map call to parse each of them into a numeric type,
         // convert the slice to a Vec for good measure, and then collect the whole mess.
-        .map(|x| x.iter().map(|y| y.parse::<u64>().unwrap()).collect_vec())
-        .collect_vec();
+        // The only way a string of digits can fail to parse is by being too big,
+        // so point at the top of the offending column if it does.
+        .map(|x| {
+            x.iter()
+                .map(|(i, y)| {
+                    y.parse::<u64>().map_err(|_| {
+                        let column = first_line.text.get(*i..*i + 1).unwrap_or(first_line.text);
+                        first_line.error(column, "a column of digits that fits in 64 bits")
+                    })
+                })
+                .collect::<Result<Vec<_>, ParseError>>()
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
 
     // Operators are much simpler: grab the last line of input
     // and separate on whitespace.
     // I'm also converting to String here just because I don't
     // fully understand lifetimes and this sidesteps that,
     // but I think a more experienced programmer could skip that.
-    let operators = input_lines
-        .last()
-        .expect("The impossible is real")
+    let operator_line = input_lines[input_lines.len() - 1];
+    let operators = operator_line
+        .text
         .split_whitespace()
         .map(|x| x.to_string())
         .collect_vec();
-    (operands, operators)
+
+    if operators.len() != operands.len() {
+        return Err(operator_line.error(
+            operator_line.text,
+            format!("one operator for each of the {} digit column groups", operands.len()),
+        ));
+    }
+
+    Ok((operands, operators))
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<u64> {
-    let (operand_columns, operators) = vertical_parse(input_lines);
-
-    let result = operators
-        .into_iter()
-        .enumerate()
-        // This is what I really should have written in part 1.
-        // I'm leaving that one as is, just for the sake of comparison.
-        .map(|(i, operator)| match operator.as_str() {
-            "+" => Ok(operand_columns
-                .get(i)
-                .expect("Mismatched number of columns and operators!")
-                .iter()
-                .sum::<u64>()),
-            "*" => Ok(operand_columns
-                .get(i)
-                .expect("Mismatched number of columns and operators!")
-                .iter()
-                .product::<u64>()),
-            _ => Err(anyhow!("Got something that wasn't an operator!")),
-        }.unwrap())
-        .sum();
+fn part2((operand_columns, operators): &(Vec<Vec<u64>>, Vec<String>)) -> Result<Answer> {
+    // This is what I really should have written in part 1.
+    // I'm leaving that one as is, just for the sake of comparison.
+    let problems = operators.iter().zip(operand_columns).collect_vec();
 
-    Ok(result)
+    parallel::try_sum(&problems, |(operator, operands)| {
+        evaluate(operator, operands.iter().copied())
+    })
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day06.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+impl Solution for Day06 {
+    const DAY: u8 = 6;
 
-    println!("{}", part1(&input_lines)?);
-    println!("{}", part2(&input_lines)?);
+    type Input = Worksheet;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input_lines = parse::lines(6, input).collect_vec();
+        if input_lines.is_empty() {
+            return Err(parse::error_at_end_of_input(6, input, "a worksheet").into());
+        }
+
+        Ok(Worksheet {
+            columns: parse_input(&input_lines)?,
+            vertical: vertical_parse(&input_lines)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(&input.columns)
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(&input.vertical)
+    }
+
+    /// Part 2 reads the numbers top to bottom, so it has to see the whole worksheet first.
+    fn stream(lines: &mut LineSource) -> Result<(Streamed<Answer>, Streamed<Answer>)> {
+        Ok((Streamed::Answer(stream_part1(lines)?), Streamed::NeedsWholeInput))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = concat!(
+        "123 328  51 64 \n",
+        " 45 64  387 23 \n",
+        "  6 98  215 314\n",
+        "*   +   *   +  \n",
+    );
+
+    #[test]
+    fn part1_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part1(&input).unwrap(), 4277556);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part2(&input).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn part1_errors_rather_than_panics_on_bad_columns() {
+        assert!(part1(&[vec![]]).is_err());
+        assert!(part1(&[vec!["x".to_string(), "+".to_string()]]).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_unknown_operator() {
+        let input_lines = parse::lines(6, "1 2\n3 4\n+ -\n").collect_vec();
+        let err = parse_input(&input_lines).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 3, "-"));
+    }
 }
@@ -1,52 +1,95 @@
-use std::{collections::{HashMap, HashSet}, fs, path::absolute};
+use std::{
+    collections::HashSet,
+    fmt,
+};
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use itertools::Itertools;
+use serde::Serialize;
+use tracing::{debug, trace};
 
-type Coord = (usize, usize);
+use crate::{
+    Solution,
+    answer::Answer,
+    grid::{Grid, Point},
+    parse::{self, Line, ParseError},
+    viz::{Animation, Frame, Tone},
+};
 
-fn parse_input(input_lines: &Vec<&str>) -> Result<(Coord, HashSet<Coord>, Coord)> {
-    let mut start_point: Option<Coord> = None;
-    let mut splitter_coords: HashSet<Coord> = HashSet::new();
+pub struct Day07;
 
-    for (y, line) in input_lines.iter().enumerate() {
-        for (x, char) in line.char_indices() {
-            if char == 'S' {
-                if start_point.is_some() {
-                    return Err(anyhow!("Found multiple start points?"));
-                }
-                start_point = Some((x, y));
-            } else if char == '^' {
-                splitter_coords.insert((x, y));
-            }
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Cell {
+    Empty,
+    Splitter,
+    Start,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let char = match self {
+            Cell::Empty => '.',
+            Cell::Splitter => '^',
+            Cell::Start => 'S',
+        };
+        write!(f, "{}", char)
     }
+}
+
+/// The manifold's layout, and where the beam enters it.
+pub type Manifold = (Grid<Cell>, Point);
 
-    if start_point.is_none() {
-        return Err(anyhow!("Could not find start point!"));
+fn parse_input(input: &str) -> Result<Manifold, ParseError> {
+    let input_lines = parse::lines(7, input).map(|line| line.text).collect_vec();
+    let grid = Grid::parse(
+        7,
+        &input_lines,
+        |char| match char {
+            '.' => Some(Cell::Empty),
+            '^' => Some(Cell::Splitter),
+            'S' => Some(Cell::Start),
+            _ => None,
+        },
+        "empty space (.), a splitter (^) or the start (S)",
+    )?;
+
+    let start_points = grid
+        .iter()
+        .filter(|(_, cell)| **cell == Cell::Start)
+        .map(|(point, _)| point)
+        .collect_vec();
+
+    let Some(&start_point) = start_points.first() else {
+        return Err(parse::error_at_end_of_input(7, input, "a start point (S) somewhere"));
+    };
+    if let Some(&extra) = start_points.get(1) {
+        // Everything in the grid is ASCII, so columns and byte offsets line up.
+        let text = input_lines[extra.y];
+        let line = Line { day: 7, index: extra.y, text };
+        return Err(line.error(&text[extra.x..extra.x + 1], "only one start point (S)"));
     }
 
-    let max_coords = (input_lines.len() - 1, input_lines[0].len() - 1);
+    debug!(?start_point, width = grid.width(), height = grid.height(), "Manifold limits");
+    trace!(
+        splitters = ?grid.iter().filter(|(_, cell)| **cell == Cell::Splitter).map(|(point, _)| point).collect_vec(),
+        "Splitter positions"
+    );
 
-    Ok((start_point.unwrap(), splitter_coords, max_coords))
+    Ok((grid, start_point))
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<usize> {
-    let mut hit_coords: HashSet<Coord> = HashSet::new();
-    let mut beamed_coords: HashSet<Coord> = HashSet::new();
-    let mut beam_coords: Vec<Coord> = Vec::new();
-    let (start_point, splitter_coords, max_coords) = parse_input(input_lines)?;
-
-    /*
-    println!("Starting at {:?}", start_point);
-    println!("Splitters at {:?}", splitter_coords);
-    println!("Limits at {:?}", max_coords);
-    */
+fn part1((grid, start_point): &Manifold) -> Result<usize> {
+    let mut hit_coords: HashSet<Point> = HashSet::new();
+    let mut beamed_coords: HashSet<Point> = HashSet::new();
+    let mut beam_coords: Vec<Point> = Vec::new();
 
-    beam_coords.push(start_point);
+    beam_coords.push(*start_point);
 
     while let Some(this_coord) = beam_coords.pop() {
-        let next_coord = (this_coord.0, this_coord.1 + 1);
+        let Some(next_coord) = this_coord.offset((0, 1)).filter(|&p| grid.contains(p)) else {
+            // This beam has left the bottom of the manifold.
+            continue;
+        };
 
         if beamed_coords.contains(&next_coord) {
             continue;
@@ -54,16 +97,11 @@ fn part1(input_lines: &Vec<&str>) -> Result<usize> {
         else {
             beamed_coords.insert(next_coord);
         }
-        
-        if splitter_coords.contains(&next_coord) {
+
+        if grid[next_coord] == Cell::Splitter {
             hit_coords.insert(next_coord);
-            if next_coord.0 > 0 {
-                beam_coords.push((next_coord.0 - 1, next_coord.1));
-            }
-            if next_coord.0 < max_coords.0 {
-                beam_coords.push((next_coord.0 + 1, next_coord.1));
-            }
-        } else if next_coord.1 < max_coords.1 {
+            beam_coords.extend(grid.neighbours(next_coord, &[(-1, 0), (1, 0)]));
+        } else {
             beam_coords.push(next_coord);
         }
     }
@@ -71,56 +109,260 @@ fn part1(input_lines: &Vec<&str>) -> Result<usize> {
     Ok(hit_coords.len())
 }
 
-fn count_splitter_paths(this_splitter: &Coord, splitter_coords: &Vec<Coord>, splitter_paths: &mut HashMap<Coord, usize>) -> usize {
-    if let Some(precalculated_paths) = splitter_paths.get(this_splitter) {
-        return *precalculated_paths;
+/// The first splitter a beam at `beam` will hit on its way down, if it hits any.
+fn next_splitter(grid: &Grid<Cell>, beam: Point) -> Option<Point> {
+    grid.column(beam.x)
+        .enumerate()
+        .skip(beam.y + 1)
+        .find(|(_, cell)| **cell == Cell::Splitter)
+        .map(|(y, _)| Point::new(beam.x, y))
+}
+
+/// Where the two beams split off by the splitter at `splitter` go. A beam split off the right
+/// of the manifold still makes its own timeline, it just can't hit anything else on the way out,
+/// so it's `None`. One split off the left isn't made at all.
+fn beams_from(grid: &Grid<Cell>, splitter: Point) -> impl Iterator<Item = Option<Point>> {
+    [splitter.offset((-1, 0)), splitter.offset((1, 0))]
+        .into_iter()
+        .flatten()
+        .map(|beam| Some(beam).filter(|&p| grid.contains(p)))
+}
+
+/// Carry the timelines in each column of the row above down into `row`, splitting them at
+/// its splitters. The timelines of beams split off the right of the manifold are added to
+/// `exited`, as [`beams_from`] has it.
+/// Every splitter can double the count, so a big enough manifold needs a bignum to count them.
+fn split_beams(
+    grid: &Grid<Cell>,
+    row: usize,
+    timelines: &[Answer],
+    exited: &mut Answer,
+    mut on_hit: impl FnMut(Point),
+) -> Vec<Answer> {
+    let mut next = vec![Answer::ZERO; grid.width()];
+    for (x, count) in timelines.iter().enumerate().filter(|(_, count)| **count != Answer::ZERO) {
+        let point = Point::new(x, row);
+        if grid[point] == Cell::Splitter {
+            on_hit(point);
+            for side in beams_from(grid, point) {
+                match side {
+                    Some(side) => next[side.x] += count.clone(),
+                    None => *exited += count.clone(),
+                }
+            }
+        } else {
+            next[x] += count.clone();
+        }
     }
 
-    let mut new_coords = vec![(this_splitter.0 + 1, this_splitter.1)];
-    if this_splitter.0 > 0 {
-        new_coords.push((this_splitter.0 - 1, this_splitter.1));
+    next
+}
+
+fn part2((grid, start_point): &Manifold) -> Result<Answer> {
+    // A beam that never splits doesn't make any timelines.
+    if next_splitter(grid, *start_point).is_none() {
+        return Ok(Answer::ZERO);
     }
 
-    let my_paths = new_coords
-        .iter()
-        .map(|(beam_x, beam_y)|
-            match splitter_coords.iter().find(|(x, y)| x == beam_x && y > beam_y) {
-                Some(next_splitter) => count_splitter_paths(next_splitter, splitter_coords, splitter_paths),
-                None => 1
-            }
-        )
-        .sum::<usize>();
+    // Work down a row at a time rather than following each beam, so that however long a
+    // chain of splitters gets, it can't run out of stack.
+    let mut timelines = vec![Answer::ZERO; grid.width()];
+    timelines[start_point.x] = Answer::ONE;
+    let mut exited = Answer::ZERO;
+    for row in start_point.y + 1..grid.height() {
+        timelines = split_beams(grid, row, &timelines, &mut exited, |splitter| {
+            trace!(?splitter, "Split the beams");
+        });
+    }
 
-    splitter_paths.insert(*this_splitter, my_paths);
+    Ok(timelines.into_iter().sum::<Answer>() + exited)
+}
 
-    my_paths
+/// The beam working its way down the manifold a row at a time, counting the timelines
+/// in each column as it goes.
+struct BeamAnimation<'a> {
+    grid: &'a Grid<Cell>,
+    row: usize,
+    timelines: Vec<Answer>,
+    /// Timelines whose beam was split off the right of the manifold.
+    exited: Answer,
+    lit: Grid<bool>,
+    hit: HashSet<Point>,
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<usize> {
-    let (start_point, mut splitter_coords, _) = parse_input(input_lines)?;
-    let mut splitter_paths: HashMap<Coord, usize> = HashMap::new();
+impl Animation for BeamAnimation<'_> {
+    fn frame(&self) -> Frame {
+        let caption = format!(
+            "Row {} of {}: {} splitters hit, {} timelines",
+            self.row + 1,
+            self.grid.height(),
+            self.hit.len(),
+            self.timelines.iter().cloned().sum::<Answer>() + self.exited.clone()
+        );
+        let mut frame = Frame::blank(self.grid.width(), self.grid.height(), caption);
+
+        for (point, cell) in self.grid.iter() {
+            let (char, tone) = match cell {
+                Cell::Start => ('S', Tone::Marked),
+                Cell::Splitter if self.hit.contains(&point) && point.y == self.row => ('^', Tone::Active),
+                Cell::Splitter if self.hit.contains(&point) => ('^', Tone::Marked),
+                Cell::Splitter => ('^', Tone::Plain),
+                Cell::Empty if self.lit[point] && point.y == self.row => ('|', Tone::Active),
+                Cell::Empty if self.lit[point] => ('|', Tone::Plain),
+                Cell::Empty => ('.', Tone::Faded),
+            };
+            frame.put(point, char, tone);
+        }
+
+        frame
+    }
 
-    let mut splitter_vec = splitter_coords.drain().collect_vec();
-    splitter_vec.sort();
+    fn step(&mut self) -> bool {
+        let row = self.row + 1;
+        if row >= self.grid.height() {
+            return false;
+        }
 
-    let result;
+        let hit = &mut self.hit;
+        let timelines = split_beams(self.grid, row, &self.timelines, &mut self.exited, |splitter| {
+            hit.insert(splitter);
+        });
 
-    if let Some(first_splitter) = splitter_vec.iter().find(|&&x| x.0 == start_point.0) {
-        result = count_splitter_paths(first_splitter, &splitter_vec, &mut splitter_paths)
+        for (x, count) in timelines.iter().enumerate() {
+            if *count != Answer::ZERO {
+                self.lit[Point::new(x, row)] = true;
+            }
+        }
+        self.timelines = timelines;
+        self.row = row;
+
+        true
     }
-    else {
-        result = 0;
+}
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Manifold;
+    type Answer1 = usize;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        part1(input)
     }
 
-    Ok(result)
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
+
+    fn animate((grid, start_point): &Self::Input) -> Option<Box<dyn Animation + '_>> {
+        let mut timelines = vec![Answer::ZERO; grid.width()];
+        timelines[start_point.x] = Answer::ONE;
+
+        Some(Box::new(BeamAnimation {
+            grid,
+            row: start_point.y,
+            timelines,
+            exited: Answer::ZERO,
+            lit: Grid::new(grid.width(), grid.height(), false),
+            hit: HashSet::new(),
+        }))
+    }
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day07.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part1(&input).unwrap(), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part2(&input).unwrap(), 40);
+    }
 
-    println!("{}", part1(&input_lines)?);
-    println!("{}", part2(&input_lines)?);
+    #[test]
+    fn animation_ends_on_both_parts() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        let mut animation = Day07::animate(&input).unwrap();
 
-    Ok(())
-}
\ No newline at end of file
+        while animation.step() {}
+        assert_eq!(animation.frame().caption, "Row 16 of 16: 21 splitters hit, 40 timelines");
+    }
+
+    #[test]
+    fn animation_counts_only_beams_split_off_the_right() {
+        // The beam split off the left edge doesn't make a timeline, as in part 2.
+        let input = Day07::parse(".S..\n.^..\n^..^\n....\n").unwrap();
+        let mut animation = Day07::animate(&input).unwrap();
+
+        while animation.step() {}
+        assert_eq!(animation.frame().caption, "Row 4 of 4: 2 splitters hit, 2 timelines");
+        assert_eq!(Day07::part2(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn wider_than_tall() {
+        let input = Day07::parse("....S..\n....^..\n.....^.\n.......\n").unwrap();
+        assert_eq!(Day07::part1(&input).unwrap(), 2);
+        assert_eq!(Day07::part2(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn only_beams_split_off_the_right_are_timelines() {
+        // A splitter on the left edge, then one on the right edge.
+        let input = Day07::parse(".S..\n.^..\n^..^\n....\n").unwrap();
+        assert_eq!(Day07::part2(&input).unwrap(), 2);
+
+        let input = Day07::parse("..S.\n..^.\n...^\n....\n").unwrap();
+        assert_eq!(Day07::part2(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn long_chains_of_splitters_dont_overflow_the_stack() {
+        // Each splitter sends one beam off the side and the other on to the next one down,
+        // but only the ones split off the right count as timelines.
+        let mut manifold = String::from("S.\n");
+        for i in 0..100_000 {
+            manifold.push_str(if i % 2 == 0 { "^.\n" } else { ".^\n" });
+        }
+
+        let input = Day07::parse(&manifold).unwrap();
+        assert_eq!(Day07::part1(&input).unwrap(), 100_000);
+        assert_eq!(Day07::part2(&input).unwrap(), 50_001);
+    }
+
+    #[test]
+    fn parse_rejects_second_start() {
+        let err = parse_input("..S..\n.....\n.S...\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+    }
+}
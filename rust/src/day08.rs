@@ -1,12 +1,23 @@
-use std::{cell::RefCell, fs, path::absolute, time::Instant};
+use std::cell::RefCell;
 
 use anyhow::{Result, anyhow};
 use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    Solution,
+    answer::Answer,
+    grid::Point,
+    parse::{self, ParseError},
+    viz::{Animation, Frame, Tone},
+};
+
+pub struct Day08;
 
 const ITER_COUNT: usize = 1000;
 
-#[derive(Debug, Clone, Copy)]
-struct Coord3D {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Coord3D {
     x: i64,
     y: i64,
     z: i64,
@@ -14,29 +25,40 @@ struct Coord3D {
 
 impl Coord3D {
     fn distance_to(&self, other: &Coord3D) -> f64 {
-        (((self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)) as f64)
-            .sqrt()
+        // Squaring in floating point keeps far-flung boxes from overflowing,
+        // and is exact for any distances we'd realistically see.
+        let [dx, dy, dz] = [
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        ]
+        .map(|d| d as f64);
+
+        (dx * dx + dy * dy + dz * dz).sqrt()
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct JunctionBox {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct JunctionBox {
     pos: Coord3D,
     circuit: usize,
 }
 
-type InputSet = (Vec<JunctionBox>, Vec<(usize, usize, f64)>, Vec<Vec<usize>>);
+pub type InputSet = (Vec<JunctionBox>, Vec<(usize, usize, f64)>, Vec<Vec<usize>>);
 
-fn parse_input(input_lines: &Vec<&str>) -> Result<InputSet> {
+fn parse_input(input: &str) -> Result<InputSet, ParseError> {
     let mut boxes = Vec::new();
     let mut box_pairs = Vec::new();
     let mut circuits: Vec<Vec<usize>> = Vec::new();
 
-    for line in input_lines {
-        let mut line_components = line.split(",");
-        let x = line_components.next().unwrap().parse::<i64>()?;
-        let y = line_components.next().unwrap().parse::<i64>()?;
-        let z = line_components.next().unwrap().parse::<i64>()?;
+    for line in parse::lines(8, input) {
+        let line_components = line.text.split(",").collect_vec();
+        let [x, y, z] = line_components[..] else {
+            return Err(line.error(line.text, "a position like 162,817,812"));
+        };
+        let x = line.number::<i64>(x)?;
+        let y = line.number::<i64>(y)?;
+        let z = line.number::<i64>(z)?;
         let coord = Coord3D { x, y, z };
         let circuit_num = circuits.len();
         let mut new_circuit = Vec::new();
@@ -54,14 +76,16 @@ fn parse_input(input_lines: &Vec<&str>) -> Result<InputSet> {
         box_pairs.push((i, j, box_a.pos.distance_to(&box_b.pos)));
     }
 
-    box_pairs.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+    box_pairs.sort_by(|a, b| a.2.total_cmp(&b.2));
 
     Ok((boxes, box_pairs, circuits))
 }
 
-fn part1((in_boxes, mut box_pairs, mut circuits): InputSet) -> Result<u64> {
+fn connect_closest((in_boxes, box_pairs, circuits): &InputSet, connections: usize) -> Result<Answer> {
+    let mut box_pairs = box_pairs.clone();
+    let mut circuits = circuits.clone();
     let boxes = in_boxes.iter().map(|e| RefCell::new(*e)).collect_vec();
-    box_pairs.truncate(ITER_COUNT);
+    box_pairs.truncate(connections);
     box_pairs.reverse();
 
     while let Some(next_pair) = box_pairs.pop() {
@@ -93,21 +117,32 @@ fn part1((in_boxes, mut box_pairs, mut circuits): InputSet) -> Result<u64> {
 
     let mut circuit_sizes = circuits
         .iter()
-        .map(|e| e.len() as u64)
+        .map(|e| e.len())
         .filter(|&e| e > 0)
         .collect_vec();
     circuit_sizes.sort();
 
-    Ok(circuit_sizes.pop().unwrap()
-        * circuit_sizes.pop().unwrap_or(1)
-        * circuit_sizes.pop().unwrap_or(1))
+    let Some(largest) = circuit_sizes.pop() else {
+        return Err(anyhow!("There are no junction boxes to connect"));
+    };
+
+    Ok([largest, circuit_sizes.pop().unwrap_or(1), circuit_sizes.pop().unwrap_or(1)]
+        .into_iter()
+        .map(Answer::from)
+        .product())
 }
 
-fn part2((in_boxes, mut box_pairs, mut circuits): InputSet) -> Result<i64> {
+fn part1(input: &InputSet) -> Result<Answer> {
+    connect_closest(input, ITER_COUNT)
+}
+
+fn part2((in_boxes, box_pairs, circuits): &InputSet) -> Result<Answer> {
+    let mut box_pairs = box_pairs.clone();
+    let mut circuits = circuits.clone();
     let boxes = in_boxes.iter().map(|e| RefCell::new(*e)).collect_vec();
     let mut circuit_count = circuits.len();
 
-    box_pairs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
+    box_pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
 
     while let Some(next_pair) = box_pairs.pop() {
         let box_a = boxes[next_pair.0].borrow_mut();
@@ -123,7 +158,7 @@ fn part2((in_boxes, mut box_pairs, mut circuits): InputSet) -> Result<i64> {
                 // We are about to join the last two circuits.
                 // This is our terminating condition.
 
-                return Ok(box_a.pos.x * box_b.pos.x);
+                return Ok(Answer::from(box_a.pos.x) * Answer::from(box_b.pos.x));
             }
 
             let mut drain_circuit = circuits.get_mut(circuit_b).unwrap().drain(..).collect_vec();
@@ -149,33 +184,228 @@ fn part2((in_boxes, mut box_pairs, mut circuits): InputSet) -> Result<i64> {
     ))
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day08.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+/// The boxes seen from above, connected a pair at a time, closest first,
+/// until they're all one circuit.
+struct CircuitAnimation<'a> {
+    boxes: &'a [JunctionBox],
+    box_pairs: &'a [(usize, usize, f64)],
+    connections: usize,
+    /// The first box of the circuit each box is in.
+    circuit_of: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    circuit_count: usize,
+    /// The smallest and largest x and y, to fit the boxes to the picture.
+    bounds: [(i64, i64); 2],
+}
+
+impl CircuitAnimation<'_> {
+    const SIZE: (usize, usize) = (72, 20);
+
+    fn project(&self, pos: Coord3D) -> Point {
+        let scale = |value: i64, (min, max): (i64, i64), size: usize| {
+            if max == min {
+                0
+            } else {
+                // The boxes can be a whole i64 apart, which only fits in a u64.
+                let (offset, range) = (value.abs_diff(min) as f64, max.abs_diff(min) as f64);
+                (offset / range * (size - 1) as f64).round() as usize
+            }
+        };
+
+        Point::new(
+            scale(pos.x, self.bounds[0], Self::SIZE.0),
+            scale(pos.y, self.bounds[1], Self::SIZE.1),
+        )
+    }
+
+    fn describe_last_connection(&self) -> String {
+        let Some(&(a, b, _)) = self.connections.checked_sub(1).map(|i| &self.box_pairs[i]) else {
+            return format!("{} boxes, {} possible connections", self.boxes.len(), self.box_pairs.len());
+        };
+        if self.circuit_count == 1 {
+            let (x_a, x_b) = (self.boxes[a].pos.x, self.boxes[b].pos.x);
+            return format!(
+                "Connection {}: joined boxes {} and {} into one circuit, {} * {} = {}",
+                self.connections,
+                a,
+                b,
+                x_a,
+                x_b,
+                Answer::from(x_a) * Answer::from(x_b)
+            );
+        }
+
+        let biggest = self
+            .members
+            .iter()
+            .map(Vec::len)
+            .sorted()
+            .rev()
+            .take(3)
+            .join(", ");
+        format!(
+            "Connection {}: boxes {} and {}, {} circuits left, the biggest {}",
+            self.connections, a, b, self.circuit_count, biggest
+        )
+    }
+}
+
+impl Animation for CircuitAnimation<'_> {
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::blank(Self::SIZE.0, Self::SIZE.1, self.describe_last_connection());
+
+        let last_pair = self.connections.checked_sub(1).map(|i| self.box_pairs[i]);
+        if let Some((a, b, _)) = last_pair {
+            let (from, to) = (self.project(self.boxes[a].pos), self.project(self.boxes[b].pos));
+            let steps = from.x.abs_diff(to.x).max(from.y.abs_diff(to.y)).max(1);
+            for step in 0..=steps {
+                let along = |start: usize, end: usize| {
+                    (start as f64 + (end as f64 - start as f64) * step as f64 / steps as f64).round() as usize
+                };
+                frame.put(Point::new(along(from.x, to.x), along(from.y, to.y)), '.', Tone::Active);
+            }
+        }
+
+        for (i, junction_box) in self.boxes.iter().enumerate() {
+            let circuit = self.circuit_of[i];
+            let tone = if self.members[circuit].len() == 1 { Tone::Faded } else { Tone::Group(circuit) };
+            frame.put(self.project(junction_box.pos), 'o', tone);
+        }
+        if let Some((a, b, _)) = last_pair {
+            for i in [a, b] {
+                frame.put(self.project(self.boxes[i].pos), '#', Tone::Active);
+            }
+        }
+
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        if self.circuit_count <= 1 {
+            return false;
+        }
+        let Some(&(a, b, _)) = self.box_pairs.get(self.connections) else {
+            return false;
+        };
+        self.connections += 1;
+
+        let (circuit_a, circuit_b) = (self.circuit_of[a], self.circuit_of[b]);
+        if circuit_a != circuit_b {
+            let moved = std::mem::take(&mut self.members[circuit_b]);
+            for &i in &moved {
+                self.circuit_of[i] = circuit_a;
+            }
+            self.members[circuit_a].extend(moved);
+            self.circuit_count -= 1;
+        }
+
+        true
+    }
+}
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
 
-    let now: Instant = Instant::now();
-    let parsed_input = parse_input(&input_lines)?;
-    let parsing_time = now.elapsed().as_secs_f64();
+    type Input = InputSet;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
 
-    println!("Data parsed in {} seconds", parsing_time);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-    let now = Instant::now();
-    let part1_result = part1(parsed_input.clone())?;
-    let part1_time = now.elapsed().as_secs_f64();
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
 
-    println!(
-        "Part 1 result: {}, took {} seconds",
-        part1_result, part1_time
-    );
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
 
-    let now = Instant::now();
-    let part2_result = part2(parsed_input.clone())?;
-    let part2_time = now.elapsed().as_secs_f64();
+    fn animate((boxes, box_pairs, circuits): &Self::Input) -> Option<Box<dyn Animation + '_>> {
+        let bounds = [|pos: Coord3D| pos.x, |pos: Coord3D| pos.y].map(|axis| {
+            boxes
+                .iter()
+                .map(|junction_box| axis(junction_box.pos))
+                .minmax()
+                .into_option()
+                .unwrap_or((0, 0))
+        });
+
+        Some(Box::new(CircuitAnimation {
+            boxes,
+            box_pairs,
+            connections: 0,
+            circuit_of: boxes.iter().map(|junction_box| junction_box.circuit).collect(),
+            members: circuits.clone(),
+            circuit_count: circuits.len(),
+            bounds,
+        }))
+    }
+}
 
-    println!(
-        "Part 2 result: {}, took {} seconds",
-        part2_result, part2_time
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689
+";
+
+    #[test]
+    fn part1_example() {
+        // The example only makes ten connections rather than a thousand.
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(connect_closest(&input, 10).unwrap(), 40);
+    }
 
-    Ok(())
+    #[test]
+    fn part2_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part2(&input).unwrap(), 25272);
+    }
+
+    #[test]
+    fn animation_ends_on_part2() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        let mut animation = Day08::animate(&input).unwrap();
+
+        while animation.step() {}
+        assert_eq!(
+            animation.frame().caption,
+            "Connection 29: joined boxes 12 and 10 into one circuit, 117 * 216 = 25272"
+        );
+    }
+
+    #[test]
+    fn animation_fits_far_flung_boxes_in_the_picture() {
+        let input = Day08::parse(&format!("{},0,0\n{},0,0\n0,1,0\n", i64::MIN, i64::MAX)).unwrap();
+        let mut animation = Day08::animate(&input).unwrap();
+
+        while animation.step() {}
+        // The boxes at either extreme are drawn at either edge.
+        let frame = animation.frame();
+        let top_row = frame.picture.row(0);
+        let (left, right) = (top_row[0].0, top_row[top_row.len() - 1].0);
+        assert!(left != ' ' && right != ' ', "{:?}", top_row);
+    }
 }
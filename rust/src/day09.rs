@@ -1,54 +1,63 @@
-use std::{fs, path::absolute, time::Instant};
-
 use anyhow::{Result, anyhow};
 use itertools::Itertools;
 
-type Coord = (u64, u64);
+use crate::{
+    Solution,
+    answer::Answer,
+    parse::{self, ParseError},
+};
 
-fn parse_input(input_lines: &Vec<&str>) -> Vec<Coord> {
-    input_lines
-        .iter()
-        .map(|&line| line.split_once(',').unwrap())
-        .map(|(x, y)| (x.parse::<u64>().unwrap(), y.parse::<u64>().unwrap()))
+pub struct Day09;
+
+pub type Coord = (u64, u64);
+
+fn parse_input(input: &str) -> Result<Vec<Coord>, ParseError> {
+    parse::lines(9, input)
+        .map(|line| {
+            let (x, y) = line
+                .text
+                .split_once(',')
+                .ok_or_else(|| line.error(line.text, "a red tile position like 7,1"))?;
+
+            Ok((line.number::<u64>(x)?, line.number::<u64>(y)?))
+        })
         .collect()
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<u64> {
-    let coords = parse_input(input_lines);
-
+fn part1(coords: &[Coord]) -> Result<Answer> {
     // Efficiency? Is that something you eat?
 
     let result = coords
         .iter()
         .combinations(2)
-        .map(|vec| {
-            let a = vec[0];
-            let b = vec[1];
-
-            (a.0.abs_diff(b.0) + 1) * (a.1.abs_diff(b.1) + 1)
-        })
+        .map(|vec| compute_area(*vec[0], *vec[1]))
         .max()
-        .unwrap();
+        .ok_or_else(|| anyhow!("It takes at least two red tiles to make a rectangle"))?;
 
     Ok(result)
 }
 
-fn compute_area(first_point: Coord, last_point: Coord) -> u64 {
-    (first_point.0.abs_diff(last_point.0) + 1) * (first_point.1.abs_diff(last_point.1) + 1)
+fn compute_area(first_point: Coord, last_point: Coord) -> Answer {
+    // A side spanning every u64 is one tile longer than a u64 can hold.
+    let width = first_point.0.abs_diff(last_point.0) as u128 + 1;
+    let height = first_point.1.abs_diff(last_point.1) as u128 + 1;
+
+    Answer::from(width) * Answer::from(height)
 }
 
-fn part2_brutest_force(input_lines: &Vec<&str>) -> Result<u64> {
-    let coords = parse_input(input_lines);
+/// Halfway between `a` and `b`, rounding up, without overflowing on the way.
+fn midpoint_up(a: u64, b: u64) -> u64 {
+    a.max(b) - (a.max(b) - a.min(b)) / 2
+}
 
+fn part2_brutest_force(coords: &[Coord]) -> Result<Answer> {
     let midpoints = coords
         .iter()
-        .enumerate()
-        .map(|(i, point)| {
-            let &next_point = coords.get(i + 1).unwrap_or(&coords[0]);
-
+        .zip(coords.iter().cycle().skip(1))
+        .map(|(point, next_point)| {
             (
-                (point.0 + next_point.0).div_ceil(2),
-                (point.1 + next_point.1).div_ceil(2),
+                midpoint_up(point.0, next_point.0),
+                midpoint_up(point.1, next_point.1),
             )
         })
         .collect_vec();
@@ -56,12 +65,12 @@ fn part2_brutest_force(input_lines: &Vec<&str>) -> Result<u64> {
     let mut all_combinations = coords
         .iter()
         .combinations(2)
-        .map(|v| (v[0], v[1]))
+        .map(|v| (compute_area(*v[0], *v[1]), *v[0], *v[1]))
         .collect_vec();
 
-    all_combinations.sort_by(|&a, &b| compute_area(*a.0, *a.1).cmp(&compute_area(*b.0, *b.1)));
+    all_combinations.sort_by(|a, b| a.0.cmp(&b.0));
 
-    while let Some((&first_point, &second_point)) = all_combinations.pop() {
+    while let Some((area, first_point, second_point)) = all_combinations.pop() {
         let left_side = first_point.0.min(second_point.0);
         let right_side = first_point.0.max(second_point.0);
         let top_side = first_point.1.min(second_point.1);
@@ -70,33 +79,57 @@ fn part2_brutest_force(input_lines: &Vec<&str>) -> Result<u64> {
         if !midpoints.iter().chain(coords.iter()).any(|&e| {
             e.0 > left_side && e.0 < right_side && e.1 > top_side && e.1 < bottom_side
         }) {
-            return Ok(compute_area(first_point, second_point));
+            return Ok(area);
         }
     }
 
     Err(anyhow!("Found nothing...somehow..."))
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day09.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Coord>;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-    let now = Instant::now();
-    let part1_result = part1(&input_lines)?;
-    let part1_time = now.elapsed().as_secs_f64();
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
 
-    let now = Instant::now();
-    let part2_result = part2_brutest_force(&input_lines)?;
-    let part2_time = now.elapsed().as_secs_f64();
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2_brutest_force(input)
+    }
+}
 
-    println!(
-        "Part 1 result: {}, took {} seconds",
-        part1_result, part1_time
-    );
-    println!(
-        "Part 2 result: {}, took {} seconds",
-        part2_result, part2_time
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+    #[test]
+    fn part1_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part1(&input).unwrap(), 50);
+    }
 
-    Ok(())
+    #[test]
+    fn part2_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part2(&input).unwrap(), 24);
+    }
 }
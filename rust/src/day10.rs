@@ -1,24 +1,37 @@
 use std::{
     collections::{HashMap, HashSet},
-    fs,
     ops::BitXorAssign,
-    path::absolute,
-    time::Instant,
 };
 
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
 use itertools::Itertools;
 use microlp::{ComparisonOp, LinearExpr, Problem};
+use serde::Serialize;
 
-#[derive(Debug)]
-struct LightPuzzle {
+use crate::{
+    Solution,
+    answer::Answer,
+    parallel,
+    parse::{self, Line, ParseError},
+    stream::{LineSource, Streamed},
+};
+
+pub struct Day10;
+
+#[derive(Debug, Serialize)]
+pub struct LightPuzzle {
     target_state: usize,
     buttons: Vec<Vec<usize>>,
     joltage: Vec<usize>,
 }
 
 impl LightPuzzle {
-    fn solve_lights(&self) -> usize {
+    fn solve_lights(&self) -> Result<usize> {
+        // All the lights start off, so there may be nothing to do.
+        if self.target_state == 0 {
+            return Ok(0);
+        }
+
         let mut node_states = HashMap::new();
         let mut unvisited_set: HashSet<usize> = HashSet::new();
         let base_state = 0;
@@ -26,11 +39,13 @@ impl LightPuzzle {
         unvisited_set.insert(base_state);
 
         loop {
-            let (shortest_node, path_length) = node_states
+            let Some((shortest_node, path_length)) = node_states
                 .iter()
                 .filter(|(k, _)| unvisited_set.contains(k))
                 .min_by_key(|(_, v)| **v)
-                .unwrap();
+            else {
+                bail!("No combination of buttons turns on the lights in {:b}", self.target_state);
+            };
             let next_path_length = path_length.to_owned() + 1;
             let next_node = shortest_node.to_owned();
             unvisited_set.remove(&next_node);
@@ -42,7 +57,7 @@ impl LightPuzzle {
                 }
 
                 if this_state == self.target_state {
-                    return next_path_length;
+                    return Ok(next_path_length);
                 }
 
                 if let Some(existing_length) = node_states.get(&this_state) {
@@ -57,7 +72,7 @@ impl LightPuzzle {
         }
     }
 
-    fn solve_joltage(&self) -> usize {
+    fn solve_joltage(&self) -> Result<Answer> {
         let mut jolt_constraints: HashMap<usize, LinearExpr> = HashMap::new();
         let mut problem = Problem::new(microlp::OptimizationDirection::Minimize);
 
@@ -73,125 +88,219 @@ impl LightPuzzle {
         }
 
         for (i, target_joltage) in self.joltage.iter().enumerate() {
-            if jolt_constraints.contains_key(&i) {
+            if let Some(constraint) = jolt_constraints.get(&i) {
                 problem.add_constraint(
-                    jolt_constraints.get(&i).unwrap().clone(),
+                    constraint.clone(),
                     ComparisonOp::Eq,
                     *target_joltage as f64,
                 );
+            } else if *target_joltage != 0 {
+                bail!("No button is wired to counter {}, so it can never reach {}", i, target_joltage);
             }
         }
 
-        let solution = problem.solve().unwrap();
-        solution
+        let solution = problem
+            .solve()
+            .map_err(|e| anyhow!("No way to press the buttons reaches the joltages: {}", e))?;
+        let presses = solution
             .iter()
             .map(|(_, value)| {
                 // Ah, floating point imprecision,
                 // my old nemesis.
-                value.round() as usize
+                Answer::from(value.round() as u128)
             })
-            .sum::<usize>()
+            .sum();
+
+        Ok(presses)
     }
 }
 
-fn parse_input(input_lines: &Vec<&str>) -> Vec<LightPuzzle> {
-    let mut result = Vec::new();
-
-    for line in input_lines {
-        let mut target_state: usize = 0;
-        let mut buttons: Vec<Vec<usize>> = Vec::new();
-        let mut joltage: Vec<usize> = Vec::new();
-
-        let line_components = line.split_whitespace();
-
-        for component in line_components {
-            if component.starts_with("[") {
-                target_state = component
-                    .strip_prefix("[")
-                    .unwrap_or(component)
-                    .strip_suffix("]")
-                    .unwrap_or(component)
-                    .char_indices()
-                    .filter_map(|(i, e)| match e {
-                        '#' => Some(2_usize.pow(i as u32)),
-                        _ => None,
-                    })
-                    .sum::<usize>();
-            } else if component.starts_with("(") {
-                buttons.push(
-                    component
-                        .strip_prefix("(")
-                        .unwrap_or(component)
-                        .strip_suffix(")")
-                        .unwrap_or(component)
-                        .split(",")
-                        .map(|e| e.parse::<usize>().unwrap())
-                        .collect_vec(),
-                );
-            } else if component.starts_with("{") {
-                joltage = component
-                    .strip_prefix("{")
-                    .unwrap_or(component)
-                    .strip_suffix("}")
-                    .unwrap_or(component)
-                    .split(",")
-                    .map(|e| e.parse::<usize>().unwrap())
-                    .collect_vec();
+/// The inside of a bracketed component like `(1,3)`, or an error if it isn't closed properly.
+fn bracketed<'a>(line: &Line, component: &'a str, open: &str, close: &str) -> Result<&'a str, ParseError> {
+    component
+        .strip_prefix(open)
+        .and_then(|e| e.strip_suffix(close))
+        .ok_or_else(|| line.error(component, format!("a closing {} with no spaces inside", close)))
+}
+
+fn number_list(line: &Line, list: &str) -> Result<Vec<usize>, ParseError> {
+    list.split(",")
+        .map(|e| line.number::<usize>(e))
+        .collect()
+}
+
+fn parse_machine(line: &Line) -> Result<LightPuzzle, ParseError> {
+    let mut light_count: Option<usize> = None;
+    let mut target_state: usize = 0;
+    let mut buttons: Vec<Vec<usize>> = Vec::new();
+    let mut joltage: Option<Vec<usize>> = None;
+
+    let line_components = line.text.split_whitespace();
+
+    for component in line_components {
+        if component.starts_with("[") {
+            if light_count.is_some() {
+                return Err(line.error(component, "only one light diagram per machine"));
+            }
+            let diagram = bracketed(line, component, "[", "]")?;
+            if diagram.is_empty() {
+                return Err(line.error(component, "at least one light"));
+            }
+            // Each light is a bit in the state, so there's only room for so many.
+            if diagram.len() > usize::BITS as usize {
+                return Err(line.error(component, format!("at most {} lights", usize::BITS)));
+            }
+
+            light_count = Some(diagram.len());
+            target_state = diagram
+                .char_indices()
+                .map(|(i, e)| match e {
+                    '#' => Ok(2_usize.pow(i as u32)),
+                    '.' => Ok(0),
+                    _ => Err(line.error(
+                        &diagram[i..i + e.len_utf8()],
+                        "a light that is on (#) or off (.)",
+                    )),
+                })
+                .sum::<Result<usize, ParseError>>()?;
+        } else if component.starts_with("(") {
+            let Some(light_count) = light_count else {
+                return Err(line.error(component, "a light diagram like [.##.] before any buttons"));
+            };
+
+            let button = number_list(line, bracketed(line, component, "(", ")")?)?;
+            if button.iter().any(|&light| light >= light_count) {
+                return Err(line.error(
+                    component,
+                    format!("buttons wired to lights 0 to {}", light_count - 1),
+                ));
+            }
+            if !button.iter().all_unique() {
+                return Err(line.error(component, "each light wired to a button only once"));
+            }
+
+            buttons.push(button);
+        } else if component.starts_with("{") {
+            if joltage.is_some() {
+                return Err(line.error(component, "only one set of joltage requirements per machine"));
+            }
+            let requirements = number_list(line, bracketed(line, component, "{", "}")?)?;
+            if light_count.is_some_and(|count| count != requirements.len()) {
+                return Err(line.error(component, "one joltage requirement per light"));
             }
+
+            joltage = Some(requirements);
+        } else {
+            return Err(line.error(
+                component,
+                "a light diagram [...], button wiring (...) or joltage requirements {...}",
+            ));
         }
-        result.push(LightPuzzle {
-            target_state,
-            buttons,
-            joltage,
-        });
     }
 
-    result
+    if light_count.is_none() {
+        return Err(line.error_at_end("a light diagram like [.##.]"));
+    }
+    let Some(joltage) = joltage else {
+        return Err(line.error_at_end("joltage requirements like {3,5,4,7}"));
+    };
+
+    Ok(LightPuzzle {
+        target_state,
+        buttons,
+        joltage,
+    })
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<usize> {
-    let puzzles = parse_input(input_lines);
+fn parse_input(input: &str) -> Result<Vec<LightPuzzle>, ParseError> {
+    parse::lines(10, input)
+        .map(|line| parse_machine(&line))
+        .collect()
+}
 
-    let result = puzzles
-        .iter()
-        .map(|puzzle| puzzle.solve_lights())
-        .sum::<usize>();
+fn part1(puzzles: &[LightPuzzle]) -> Result<Answer> {
+    // Every machine is its own puzzle, so they can all be solved in parallel.
+    parallel::try_sum(puzzles, LightPuzzle::solve_lights)
+}
 
-    Ok(result)
+fn part2(puzzles: &[LightPuzzle]) -> Result<Answer> {
+    // Every machine is its own puzzle, so they can all be solved in parallel.
+    parallel::try_sum(puzzles, LightPuzzle::solve_joltage)
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<usize> {
-    let puzzles = parse_input(input_lines);
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<LightPuzzle>;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
+
+    fn stream(lines: &mut LineSource) -> Result<(Streamed<Answer>, Streamed<Answer>)> {
+        let (mut part1, mut part2) = (Ok(Answer::ZERO), Ok(Answer::ZERO));
 
-    let result = puzzles
-        .iter()
-        .map(|puzzle| puzzle.solve_joltage())
-        .sum::<usize>();
+        while let Some(line) = lines.next_line()? {
+            let puzzle = parse_machine(&line)?;
+            part1 = part1.and_then(|total| Ok(total + puzzle.solve_lights()?.into()));
+            part2 = part2.and_then(|total| Ok(total + puzzle.solve_joltage()?));
+        }
 
-    Ok(result)
+        Ok((part1.into(), part2.into()))
+    }
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day10.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let now = Instant::now();
-    let part1_result = part1(&input_lines)?;
-    let part1_time = now.elapsed().as_secs_f64();
+    const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
 
-    println!(
-        "Part 1 result: {}, took {} seconds",
-        part1_result, part1_time
-    );
+    #[test]
+    fn part1_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&input).unwrap(), 7);
+    }
 
-    let now = Instant::now();
-    let part2_result = part2(&input_lines)?;
-    let part2_time = now.elapsed().as_secs_f64();
+    #[test]
+    fn part2_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&input).unwrap(), 33);
+    }
 
-    println!(
-        "Part 2 result: {}, took {} seconds",
-        part2_result, part2_time
-    );
+    #[test]
+    fn parse_rejects_button_for_missing_light() {
+        let err = parse_input("[.#] (0,2) {1,2}\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 6, "(0,2)"));
+    }
+
+    #[test]
+    fn parse_rejects_light_wired_twice() {
+        let err = parse_input("[.#.] (0,2,2) {1,2,3}\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 7, "(0,2,2)"));
+    }
 
-    Ok(())
+    #[test]
+    fn unreachable_targets_are_errors() {
+        let input = Day10::parse("[.#] (0) {0,2}\n").unwrap();
+        assert!(Day10::part1(&input).is_err());
+        assert!(Day10::part2(&input).is_err());
+
+        let input = Day10::parse("[..] (0) {0,0}\n").unwrap();
+        assert_eq!(Day10::part1(&input).unwrap(), 0);
+    }
 }
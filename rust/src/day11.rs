@@ -1,146 +1,279 @@
-use std::{cell::RefCell, collections::HashMap, fs, path::absolute, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
+use serde::Serialize;
 
-struct WireNode {
+use crate::{
+    Solution,
+    answer::Answer,
+    parse::{self, Line, ParseError},
+    stream::{LineSource, Streamed},
+};
+
+pub struct Day11;
+
+pub type NodeMap = HashMap<String, WireNode>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WireNode {
     name: String,
-    paths_out: Option<usize>,
     exits: Vec<String>,
-    milestone_paths_out: HashMap<[bool; 2], usize>,
 }
 
 impl WireNode {
-    fn get_paths_out(&mut self, node_map: &HashMap<String, RefCell<WireNode>>) -> usize {
-        if self.paths_out.is_some() {
-            self.paths_out.unwrap()
-        } else if self.exits.contains(&String::from("out")) {
-            self.paths_out = Some(1);
-            1
-        } else {
-            let total_paths_out = self
-                .exits
-                .iter()
-                .map(|e| {
-                    node_map
-                        .get(e)
-                        .unwrap()
-                        .borrow_mut()
-                        .get_paths_out(node_map)
-                })
-                .sum::<usize>();
-            self.paths_out = Some(total_paths_out);
-            total_paths_out
-        }
+    fn leads_out(&self) -> bool {
+        self.exits.iter().any(|exit| exit == "out")
     }
+}
 
-    fn get_paths_out_with_milestones(
-        &mut self,
-        node_map: &HashMap<String, RefCell<WireNode>>,
-        hit_dac: &bool,
-        hit_fft: &bool,
-    ) -> usize {
-        if self.exits.contains(&String::from("out")) {
-            if *hit_dac && *hit_fft {
-                return 1;
-            } else {
-                return 0;
-            }
-        }
-
-        let my_hit_dac = if self.name == "dac" { true } else { *hit_dac };
+/// Count the paths from `start` to out, carrying some state along each one.
+///
+/// Reaching a device that leads out ends a path, and `at_exit` says what it's worth given
+/// the state it arrived with. Any other device passes on the state as `passing` changes it.
+/// Counts are memoized per device and arriving state. The search keeps its own stack, since
+/// a long enough chain of devices would overflow the real one.
+fn count_paths<S: Copy + Eq + Hash>(
+    node_map: &NodeMap,
+    start: &str,
+    initial: S,
+    at_exit: impl Fn(S) -> Answer,
+    passing: impl Fn(&WireNode, S) -> S,
+) -> Result<Answer> {
+    let mut counted: HashMap<(&str, S), Answer> = HashMap::new();
+    // The devices on the path being explored. Meeting one again means the wiring loops.
+    let mut on_path: HashSet<&str> = HashSet::new();
+    // Each device to count, and whether its exits have been queued up yet.
+    let mut stack = vec![(start, initial, false)];
 
-        let my_hit_fft = if self.name == "fft" { true } else { *hit_fft };
+    while let Some(&(name, state, queued)) = stack.last() {
+        if counted.contains_key(&(name, state)) {
+            stack.pop();
+            continue;
+        }
+        let node = node_map
+            .get(name)
+            .ok_or_else(|| anyhow!("There's no device called {}", name))?;
 
-        let milestone_key = [my_hit_dac, my_hit_fft];
+        if node.leads_out() {
+            counted.insert((name, state), at_exit(state));
+            stack.pop();
+            continue;
+        }
 
-        if let Some(milestone_paths) = self.milestone_paths_out.get(&milestone_key) {
-            return *milestone_paths;
+        let next_state = passing(node, state);
+        if !queued {
+            if !on_path.insert(name) {
+                bail!("The wiring loops back around to {}", name);
+            }
+            stack.last_mut().unwrap().2 = true;
+            stack.extend(node.exits.iter().map(|exit| (exit.as_str(), next_state, false)));
+            continue;
         }
 
-        let total_paths_out = self
+        // The paths double at every fork, so a big enough tangle needs a bignum to count them.
+        let paths = node
             .exits
             .iter()
-            .map(|e| {
-                node_map
-                    .get(e)
-                    .unwrap()
-                    .borrow_mut()
-                    .get_paths_out_with_milestones(node_map, &my_hit_dac, &my_hit_fft)
-            })
-            .sum::<usize>();
-
-        self.milestone_paths_out
-            .insert(milestone_key, total_paths_out);
-        total_paths_out
+            .map(|exit| counted[&(exit.as_str(), next_state)].clone())
+            .sum::<Answer>();
+        counted.insert((name, state), paths);
+        on_path.remove(name);
+        stack.pop();
     }
+
+    Ok(counted[&(start, initial)].clone())
 }
 
-fn parse_input(input_lines: &Vec<&str>) -> HashMap<String, RefCell<WireNode>> {
-    let mut node_map = HashMap::new();
+/// Builds up the wiring a line at a time, so it can come straight from a stream
+/// without keeping the input's text around.
+#[derive(Default)]
+struct WiringBuilder {
+    node_map: NodeMap,
+    /// Outputs to devices that haven't been listed yet, with an error pointing at
+    /// the first place each one turned up, in case they never are.
+    unknown_exits: HashMap<String, ParseError>,
+}
+
+impl WiringBuilder {
+    fn add(&mut self, line: &Line) -> Result<(), ParseError> {
+        let mut line_components = line.text.split_whitespace();
+        let Some(name_component) = line_components.next() else {
+            return Err(line.error_at_end("a device and its outputs, like aaa: you hhh"));
+        };
+        let node_name = name_component
+            .strip_suffix(":")
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| line.error(name_component, "a device name followed by a colon"))?;
+        if self.node_map.contains_key(node_name) {
+            return Err(line.error(name_component, "each device to be listed only once"));
+        }
+        self.unknown_exits.remove(node_name);
 
-    for line in input_lines {
-        let mut line_components = line.split_whitespace();
-        let node_name = line_components.next().unwrap().strip_suffix(":").unwrap();
         let mut exits = Vec::new();
-        for exit in line_components.map(|e| e.to_string()) {
-            exits.push(exit);
+        for exit in line_components {
+            if exit != "out" && !self.node_map.contains_key(exit) && exit != node_name {
+                self.unknown_exits.entry(exit.to_string()).or_insert_with(|| {
+                    line.error(exit, "a device listed elsewhere in the input, or out")
+                });
+            }
+            exits.push(exit.to_string());
         }
         let this_node = WireNode {
             name: node_name.to_string(),
-            paths_out: None,
-            milestone_paths_out: HashMap::new(),
             exits,
         };
 
-        node_map.insert(node_name.to_string(), RefCell::new(this_node));
+        self.node_map.insert(node_name.to_string(), this_node);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<NodeMap, ParseError> {
+        // Every output has to lead somewhere we know about, or the path counting has nowhere to go.
+        match self.unknown_exits.into_values().min_by_key(|e| (e.line, e.column)) {
+            Some(e) => Err(e),
+            None => Ok(self.node_map),
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<NodeMap, ParseError> {
+    let mut wiring = WiringBuilder::default();
+
+    for line in parse::lines(11, input) {
+        wiring.add(&line)?;
     }
 
-    node_map
+    wiring.finish()
 }
 
-fn part1(input_lines: &Vec<&str>) -> Result<usize> {
-    let node_map = parse_input(input_lines);
-    let result = node_map
-        .get("you")
-        .unwrap()
-        .borrow_mut()
-        .get_paths_out(&node_map);
+fn part1(node_map: &NodeMap) -> Result<Answer> {
+    count_paths(node_map, "you", (), |()| Answer::ONE, |_, ()| ())
+}
 
-    Ok(result)
+fn part2(node_map: &NodeMap) -> Result<Answer> {
+    // Whether the path has been through dac and fft so far. It only counts if it has both
+    // by the time it leads out.
+    count_paths(
+        node_map,
+        "svr",
+        [false, false],
+        |[hit_dac, hit_fft]| if hit_dac && hit_fft { Answer::ONE } else { Answer::ZERO },
+        |node, [hit_dac, hit_fft]| [hit_dac || node.name == "dac", hit_fft || node.name == "fft"],
+    )
 }
 
-fn part2(input_lines: &Vec<&str>) -> Result<usize> {
-    let node_map = parse_input(input_lines);
-    let result = node_map
-        .get("svr")
-        .unwrap()
-        .borrow_mut()
-        .get_paths_out_with_milestones(&node_map, &false, &false);
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = NodeMap;
+    type Answer1 = Answer;
+    type Answer2 = Answer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        part2(input)
+    }
 
-    Ok(result)
+    /// The wiring has to be complete before any paths can be counted, but it can at least
+    /// be built without ever holding the input's text.
+    fn stream(lines: &mut LineSource) -> Result<(Streamed<Answer>, Streamed<Answer>)> {
+        let mut wiring = WiringBuilder::default();
+        while let Some(line) = lines.next_line()? {
+            wiring.add(&line)?;
+        }
+        let node_map = wiring.finish()?;
+
+        Ok((part1(&node_map).into(), part2(&node_map).into()))
+    }
 }
 
-fn main() -> Result<()> {
-    let input_string = fs::read_to_string(absolute("../input_files/day11.input.txt")?)?;
-    let input_lines: Vec<&str> = input_string.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let now = Instant::now();
-    let part1_result = part1(&input_lines)?;
-    let part1_time = now.elapsed().as_secs_f64();
+    const PART1_EXAMPLE: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+";
 
-    println!(
-        "Part 1 result: {}, took {} seconds",
-        part1_result, part1_time
-    );
+    const PART2_EXAMPLE: &str = "\
+svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out
+";
 
-    let now = Instant::now();
-    let part2_result = part2(&input_lines)?;
-    let part2_time = now.elapsed().as_secs_f64();
+    #[test]
+    fn part1_example() {
+        let input = Day11::parse(PART1_EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&input).unwrap(), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day11::parse(PART2_EXAMPLE).unwrap();
+        assert_eq!(Day11::part2(&input).unwrap(), 2);
+    }
 
-    println!(
-        "Part 2 result: {}, took {} seconds",
-        part2_result, part2_time
-    );
+    #[test]
+    fn parse_rejects_unknown_device() {
+        let err = parse_input("aaa: bbb\nyou: aaa\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 6, "bbb"));
+    }
 
-    Ok(())
+    #[test]
+    fn long_chains_of_devices_dont_overflow_the_stack() {
+        let name = |i| match i {
+            50_000 => "dac".to_string(),
+            150_000 => "fft".to_string(),
+            _ => format!("n{}", i),
+        };
+        // Listed from the end of the chain back, so every device's exit is already known.
+        let mut wiring = String::from("n200000: out\n");
+        for i in (0..200_000).rev() {
+            wiring.push_str(&format!("{}: {}\n", name(i), name(i + 1)));
+        }
+        wiring.push_str("svr: n0\nyou: n0\n");
+
+        let input = Day11::parse(&wiring).unwrap();
+        assert_eq!(Day11::part1(&input).unwrap(), 1);
+        assert_eq!(Day11::part2(&input).unwrap(), 1);
+    }
+
+    #[test]
+    fn loops_and_missing_devices_are_errors() {
+        let input = Day11::parse("you: aaa\naaa: bbb\nbbb: aaa out\n").unwrap();
+        assert!(Day11::part1(&input).is_ok());
+
+        let input = Day11::parse("you: aaa\naaa: bbb\nbbb: aaa\n").unwrap();
+        assert!(Day11::part1(&input).is_err());
+        assert!(Day11::part2(&input).is_err());
+    }
 }
@@ -0,0 +1,144 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::input;
+
+pub const YEAR: u16 = 2025;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the adventofcode.com `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable overriding [`DEFAULT_BASE_URL`], mostly so tests can use a local server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Advent of Code asks automated tools to say who they are.
+#[cfg(not(target_arch = "wasm32"))]
+const USER_AGENT: &str = "github.com/HyperGeek7/AdventOfCode2025 input fetcher";
+
+/// Everything needed to download inputs and decide where to keep them.
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+}
+
+/// Where the session token is kept when it isn't in the environment:
+/// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+pub fn session_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|config| config.join("aoc").join("session"))
+}
+
+/// The directory fetched inputs get saved to: the first input directory that already exists,
+/// or else `input_files/` beside the `rust/` directory, which is where the inputs have always lived.
+pub fn default_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(input::INPUT_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+
+    input::candidate_dirs()
+        .into_iter()
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(input::INPUT_DIR_NAME)
+        })
+}
+
+impl FetchConfig {
+    /// Read the configuration from `$AOC_BASE_URL`, `$AOC_SESSION` and the [`session_file`].
+    pub fn from_env() -> FetchConfig {
+        let session = env::var(SESSION_VAR)
+            .ok()
+            .or_else(|| session_file().and_then(|path| fs::read_to_string(path).ok()));
+
+        FetchConfig {
+            base_url: env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL)),
+            session: session
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+            cache_dir: default_cache_dir(),
+        }
+    }
+
+    pub fn input_url(&self, day: u8) -> String {
+        format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            YEAR,
+            day
+        )
+    }
+
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(input::file_name(day))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn download(config: &FetchConfig, day: u8) -> Result<String> {
+    let Some(session) = &config.session else {
+        bail!(
+            "No session token to download with. Set ${} or put it in {}",
+            SESSION_VAR,
+            session_file().map_or(String::from("a session file"), |p| p.display().to_string())
+        );
+    };
+
+    let url = config.input_url(day);
+    let response = ureq::get(&url)
+        .header("Cookie", format!("session={}", session))
+        .header("User-Agent", USER_AGENT)
+        .call();
+
+    let body = match response {
+        Ok(mut response) => response.body_mut().read_to_string()?,
+        Err(ureq::Error::StatusCode(400)) => {
+            bail!("{} rejected the session token; it may have expired", url)
+        }
+        Err(ureq::Error::StatusCode(404)) => bail!("{} not found; is day {} out yet?", url, day),
+        Err(e) => return Err(e).with_context(|| format!("Could not download {}", url)),
+    };
+
+    if body.trim().is_empty() {
+        bail!("{} sent back an empty input", url);
+    }
+
+    Ok(body)
+}
+
+/// WebAssembly has no sockets to download with; the browser page is given its input instead.
+#[cfg(target_arch = "wasm32")]
+fn download(_config: &FetchConfig, day: u8) -> Result<String> {
+    bail!("Can't download the input for day {} from WebAssembly", day)
+}
+
+/// Make sure the input for `day` is in the cache, downloading it only if it isn't there yet.
+/// Gives the path to the cached file either way.
+pub fn fetch(config: &FetchConfig, day: u8) -> Result<PathBuf> {
+    let path = config.cache_path(day);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    let body = download(config, day)?;
+
+    fs::create_dir_all(&config.cache_dir)
+        .with_context(|| format!("Could not create {}", config.cache_dir.display()))?;
+    // Write to a temporary name first so an interrupted download never looks cached.
+    let partial = path.with_extension("partial");
+    fs::write(&partial, body)?;
+    fs::rename(&partial, &path)?;
+
+    Ok(path)
+}
@@ -0,0 +1,494 @@
+//! Random puzzle inputs in each day's format, for stress tests and benchmarks.
+//!
+//! Every generator takes a `size`, which is roughly how many of the day's main things
+//! (lines, ranges, machines...) to produce, and an RNG seeded for reproducible output.
+//! Days 7, 9 and 11 assume things of their inputs that the generators normally keep to,
+//! but can break on purpose to see how the solvers cope; see [`Guarantees`].
+
+use std::{collections::HashSet, fmt::Write};
+
+use anyhow::{Result, bail};
+use itertools::Itertools;
+use rand::{Rng, SeedableRng, seq::SliceRandom, seq::index};
+use rand_chacha::ChaCha8Rng;
+
+/// About how big each day's real input is, in the units its `size` counts.
+pub fn typical_size(day: u8) -> Option<usize> {
+    Some(match day {
+        1 => 4000,
+        2 => 35,
+        3 => 200,
+        4 => 140,
+        5 => 190,
+        6 => 1000,
+        7 => 141,
+        8 => 1000,
+        9 => 500,
+        10 => 160,
+        11 => 600,
+        _ => return None,
+    })
+}
+
+/// Whether to keep to what days 7, 9 and 11 assume of their inputs: a single `S` in the
+/// manifold, a closed loop of red tiles, and wiring with no loops in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guarantees {
+    Kept,
+    /// Two `S`s, a loop with its last corner missing, and wiring that loops back to `svr`.
+    Broken,
+}
+
+/// A random input for `day`, the same every time for the same `size` and `seed`.
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String> {
+    generate_with(day, size, seed, Guarantees::Kept)
+}
+
+/// [`generate`], keeping or breaking the [`Guarantees`] the day relies on. Only days 7, 9
+/// and 11 have any to break.
+pub fn generate_with(day: u8, size: usize, seed: u64, guarantees: Guarantees) -> Result<String> {
+    if guarantees == Guarantees::Broken && ![7, 9, 11].contains(&day) {
+        bail!("Day {} doesn't assume anything of its input to break", day);
+    }
+    if size == 0 {
+        bail!("There's no input with a size of 0; every day needs at least one of something");
+    }
+    let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+
+    Ok(match day {
+        1 => rotations(rng, size),
+        2 => id_ranges(rng, size),
+        3 => banks(rng, size),
+        4 => roll_grid(rng, size),
+        5 => inventory(rng, size),
+        6 => worksheet(rng, size),
+        7 => manifold(rng, size, guarantees),
+        8 => boxes(rng, size),
+        9 => polygon(rng, size, guarantees),
+        10 => machines(rng, size),
+        11 => wiring(rng, size, guarantees)?,
+        _ => bail!("No input generator for day {}", day),
+    })
+}
+
+/// Day 01: `size` dial rotations like `L68`.
+fn rotations(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let dir = if rng.random_bool(0.5) { 'L' } else { 'R' };
+            format!("{}{}\n", dir, rng.random_range(1..1000))
+        })
+        .collect()
+}
+
+/// Day 02: `size` comma-separated ID ranges, none too wide to brute force.
+fn id_ranges(rng: &mut impl Rng, size: usize) -> String {
+    let ranges = (0..size)
+        .map(|_| {
+            let digits = rng.random_range(1..=10);
+            let first = rng.random_range(10_u64.pow(digits - 1)..10_u64.pow(digits));
+            format!("{}-{}", first, first + rng.random_range(0..100_000))
+        })
+        .join(",");
+
+    format!("{}\n", ranges)
+}
+
+/// Day 03: `size` banks of 100 battery digits.
+fn banks(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let bank: String = (0..100)
+                .map(|_| char::from(b'0' + rng.random_range(1..=9)))
+                .collect();
+            bank + "\n"
+        })
+        .collect()
+}
+
+/// Day 04: a `size` by `size` floor, mostly covered in rolls.
+fn roll_grid(rng: &mut impl Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let row: String = (0..size)
+                .map(|_| if rng.random_bool(0.6) { '@' } else { '.' })
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+/// Day 05: `size` fresh ranges, some overlapping, then five times as many ingredient IDs.
+fn inventory(rng: &mut impl Rng, size: usize) -> String {
+    const MAX_ID: u64 = 500_000_000_000_000;
+
+    let mut input = String::new();
+    for _ in 0..size {
+        let low = rng.random_range(1..MAX_ID);
+        let high = low + rng.random_range(0..MAX_ID / 100);
+        writeln!(input, "{}-{}", low, high).unwrap();
+    }
+    input.push('\n');
+    for _ in 0..size * 5 {
+        writeln!(input, "{}", rng.random_range(1..MAX_ID + MAX_ID / 100)).unwrap();
+    }
+
+    input
+}
+
+/// Day 06: a worksheet of `size` problems, each four numbers tall, with the numbers in
+/// each problem lined up on the left or the right at random.
+fn worksheet(rng: &mut impl Rng, size: usize) -> String {
+    let mut rows = vec![String::new(); 5];
+
+    for problem in 0..size {
+        let numbers = (0..4)
+            .map(|_| rng.random_range(1..10_000_u64).to_string())
+            .collect_vec();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left_aligned = rng.random_bool(0.5);
+        let operator = if rng.random_bool(0.5) { "+" } else { "*" };
+
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            if left_aligned {
+                write!(row, "{:<width$}", number).unwrap();
+            } else {
+                write!(row, "{:>width$}", number).unwrap();
+            }
+        }
+        write!(rows[4], "{:<width$}", operator).unwrap();
+    }
+
+    rows.into_iter().map(|row| row + "\n").collect()
+}
+
+/// Day 07: a `size` wide manifold with exactly one `S` in the top row, unless the guarantees
+/// are broken, when there's another in the corner. Splitters sit on every other row, and never
+/// at the edges, so a split beam always has somewhere to go.
+fn manifold(rng: &mut impl Rng, size: usize, guarantees: Guarantees) -> String {
+    let width = size.max(3);
+    let mut rows = vec![vec!['.'; width]; width];
+    rows[0][width / 2] = 'S';
+    if guarantees == Guarantees::Broken {
+        rows[0][0] = 'S';
+    }
+
+    for row in rows.iter_mut().skip(2).step_by(2) {
+        for cell in &mut row[1..width - 1] {
+            if rng.random_bool(0.3) {
+                *cell = '^';
+            }
+        }
+    }
+
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Day 08: `size` junction boxes scattered through a cube 100,000 units across, and at least
+/// two, so there's something to connect.
+fn boxes(rng: &mut impl Rng, size: usize) -> String {
+    (0..size.max(2))
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.random_range(0..100_000));
+            format!("{},{},{}\n", x, y, z)
+        })
+        .collect()
+}
+
+/// Distinct sorted x coordinates from `left` to `right` inclusive, with `count - 1` between them.
+fn columns(rng: &mut impl Rng, left: u64, right: u64, count: usize) -> Vec<u64> {
+    let inside = (right - left - 1) as usize;
+    let mut xs = index::sample(rng, inside, (count - 1).min(inside))
+        .into_iter()
+        .map(|i| left + 1 + i as u64)
+        .collect_vec();
+    xs.sort();
+
+    [left].into_iter().chain(xs).chain([right]).collect()
+}
+
+/// Heights for each gap between `xs`, drawn from `range`, never the same twice in a row
+/// so every corner of the outline really is a corner.
+fn heights(rng: &mut impl Rng, xs: &[u64], range: std::ops::Range<u64>) -> Vec<u64> {
+    let mut heights: Vec<u64> = Vec::new();
+
+    for _ in 1..xs.len() {
+        let height = loop {
+            let height = rng.random_range(range.clone());
+            if heights.last() != Some(&height) {
+                break height;
+            }
+        };
+        heights.push(height);
+    }
+
+    heights
+}
+
+/// Day 09: a closed rectilinear loop of about `size` red tiles, listed in order around it.
+/// The top and bottom are each a random skyline, so the loop never crosses itself.
+/// Breaking the guarantees leaves off the last corner, so the loop doesn't close.
+fn polygon(rng: &mut impl Rng, size: usize, guarantees: Guarantees) -> String {
+    const SPAN: u64 = 100_000;
+    const MIDDLE: u64 = SPAN / 2;
+
+    let bars = (size / 4).max(1);
+    let top_xs = columns(rng, 1, SPAN, bars);
+    let top = heights(rng, &top_xs, MIDDLE + 1..SPAN);
+    let bottom_xs = columns(rng, 1, SPAN, bars);
+    let bottom = heights(rng, &bottom_xs, 1..MIDDLE);
+
+    let mut corners = Vec::new();
+    // Left to right along the top...
+    for (i, &height) in top.iter().enumerate() {
+        corners.push((top_xs[i], height));
+        corners.push((top_xs[i + 1], height));
+    }
+    // ...then right to left along the bottom, which joins back up with the first corner.
+    for (i, &depth) in bottom.iter().enumerate().rev() {
+        corners.push((bottom_xs[i + 1], depth));
+        corners.push((bottom_xs[i], depth));
+    }
+    if guarantees == Guarantees::Broken {
+        corners.pop();
+    }
+
+    corners
+        .into_iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+/// Day 10: `size` machines. The lights and joltages are worked out from real button presses,
+/// so every machine can be solved.
+fn machines(rng: &mut impl Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let lights = rng.random_range(4..=10);
+        let buttons = (0..rng.random_range(lights / 2..=lights + 3))
+            .map(|_| {
+                let wired = rng.random_range(1..=lights);
+                let mut button = index::sample(rng, lights, wired).into_vec();
+                button.sort();
+                button
+            })
+            .collect_vec();
+
+        let mut on = vec![false; lights];
+        let mut joltage = vec![0; lights];
+        for button in &buttons {
+            let presses = rng.random_range(0..20);
+            for &light in button {
+                on[light] ^= presses % 2 == 1;
+                joltage[light] += presses;
+            }
+        }
+
+        let diagram: String = on.iter().map(|&on| if on { '#' } else { '.' }).collect();
+        let buttons = buttons
+            .iter()
+            .map(|button| format!("({})", button.iter().join(",")))
+            .join(" ");
+        writeln!(
+            input,
+            "[{}] {} {{{}}}",
+            diagram,
+            buttons,
+            joltage.iter().join(",")
+        )
+        .unwrap();
+    }
+
+    input
+}
+
+/// Day 11: `size` devices wired into an acyclic graph. `svr` comes first, then `you`,
+/// with `fft` and `dac` later on, and every device eventually reaches `out`.
+/// Breaking the guarantees wires the last device but one back to `svr` as well, which loops
+/// everything after `svr` round on itself. (The last one leads `out`, which ends the path.)
+fn wiring(rng: &mut impl Rng, size: usize, guarantees: Guarantees) -> Result<String> {
+    const SPECIAL: [&str; 4] = ["svr", "you", "fft", "dac"];
+
+    // Three-letter names, less the ones that mean something.
+    if size < SPECIAL.len() || size > 26 * 26 * 26 - SPECIAL.len() - 1 {
+        bail!(
+            "Day 11 needs between {} and {} devices",
+            SPECIAL.len(),
+            26 * 26 * 26 - 5
+        );
+    }
+
+    let mut taken: HashSet<String> = SPECIAL.iter().map(|name| name.to_string()).collect();
+    taken.insert(String::from("out"));
+
+    let mut names = Vec::with_capacity(size);
+    while names.len() < size - SPECIAL.len() {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.random_range(0..26)))
+            .collect();
+        if taken.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names.insert(0, String::from("svr"));
+    names.insert(1, String::from("you"));
+    // Keep both after `you`, however few devices there are.
+    names.insert((size / 3).max(2), String::from("fft"));
+    names.insert((2 * size / 3).max(3), String::from("dac"));
+
+    // Each device feeds the next, so everything is reachable from everything before it.
+    // The odd extra shortcut makes for more paths, though rarely so many that they need a bignum.
+    // The chain is as long as there are devices, so the biggest sizes are a test of depth too.
+    let shortcut_chance = (30.0 / size as f64).min(0.5);
+    let mut lines = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let mut exits = vec![names.get(i + 1).map_or("out", String::as_str)];
+            if i + 2 < size && rng.random_bool(shortcut_chance) {
+                exits.push(&names[rng.random_range(i + 2..size.min(i + 6))]);
+            }
+            if guarantees == Guarantees::Broken && i + 2 == size {
+                exits.push(&names[0]);
+            }
+            format!("{}: {}\n", name, exits.join(" "))
+        })
+        .collect_vec();
+    lines.shuffle(rng);
+
+    Ok(lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{Part, Solution, day07::Day07, day09::Day09};
+
+    #[test]
+    fn every_day_solves_its_generated_input() {
+        for day in crate::DAYS {
+            let input = generate(day.day(), 6, 7).unwrap();
+            let parsed = day.parse(&input).unwrap();
+
+            for part in Part::BOTH {
+                parsed.solve(part).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn every_day_solves_its_smallest_inputs() {
+        for day in crate::DAYS {
+            for size in 1..=5 {
+                // Day 11 can't fit its four named devices into less, and says so.
+                let Ok(input) = generate(day.day(), size, 7) else {
+                    assert!(
+                        day.day() == 11 && size < 4,
+                        "Day {} size {}",
+                        day.day(),
+                        size
+                    );
+                    continue;
+                };
+                let parsed = day.parse(&input).unwrap();
+
+                for part in Part::BOTH {
+                    parsed
+                        .solve(part)
+                        .unwrap_or_else(|e| panic!("Day {} size {}: {:#}", day.day(), size, e));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn size_zero_is_an_error() {
+        for day in crate::DAYS {
+            assert!(generate(day.day(), 0, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(10, 5, 1).unwrap(), generate(10, 5, 1).unwrap());
+        assert_ne!(generate(10, 5, 1).unwrap(), generate(10, 5, 2).unwrap());
+    }
+
+    #[test]
+    fn polygon_is_a_closed_rectilinear_loop() {
+        let input = generate(9, 40, 3).unwrap();
+        let corners = Day09::parse(&input).unwrap();
+
+        for (i, a) in corners.iter().enumerate() {
+            let b = corners[(i + 1) % corners.len()];
+            // Edges alternate between horizontal and vertical all the way around.
+            assert!(
+                if i % 2 == 0 { a.1 == b.1 } else { a.0 == b.0 },
+                "{:?} to {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn wiring_keeps_its_devices_in_order() {
+        for size in 4..=12 {
+            let input = generate(11, size, 5).unwrap();
+            let first_exits: HashMap<&str, &str> = input
+                .lines()
+                .filter_map(|line| line.split_once(": "))
+                .map(|(name, exits)| (name, exits.split(' ').next().unwrap()))
+                .collect();
+
+            // Every device's first exit is the next one along the chain.
+            let chain = std::iter::successors(Some("svr"), |name| first_exits.get(name).copied())
+                .collect_vec();
+            let position = |name| chain.iter().position(|&n| n == name).unwrap();
+            assert!(
+                position("svr") < position("you")
+                    && position("you") < position("fft")
+                    && position("fft") < position("dac"),
+                "{:?}",
+                chain
+            );
+        }
+    }
+
+    #[test]
+    fn broken_guarantees_are_caught() {
+        let manifold = generate_with(7, 10, 1, Guarantees::Broken).unwrap();
+        assert!(Day07::parse(&manifold).is_err());
+
+        let wiring = generate_with(11, 10, 1, Guarantees::Broken).unwrap();
+        let parsed = crate::get_day(11).unwrap().parse(&wiring).unwrap();
+        for part in Part::BOTH {
+            assert!(parsed.solve(part).is_err());
+        }
+
+        // The last corner doesn't line up with the first any more.
+        let corners = Day09::parse(&generate_with(9, 40, 3, Guarantees::Broken).unwrap()).unwrap();
+        let (first, last) = (corners[0], corners[corners.len() - 1]);
+        assert!(first.0 != last.0 && first.1 != last.1);
+
+        assert!(generate_with(1, 10, 1, Guarantees::Broken).is_err());
+    }
+
+    #[test]
+    fn biggest_wiring_solves() {
+        let input = generate(11, 26 * 26 * 26 - 5, 1).unwrap();
+        let parsed = crate::get_day(11).unwrap().parse(&input).unwrap();
+
+        for part in Part::BOTH {
+            parsed.solve(part).unwrap();
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! A dense, rectangular 2D grid for the days whose input is a map.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::parse::{self, Line, ParseError};
+
+/// A position in a [`Grid`]. `x` counts columns from the left, `y` rows from the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// A step from one point to another, as `(dx, dy)`.
+pub type Offset = (isize, isize);
+
+/// Up, left, right and down.
+pub const ORTHOGONAL: &[Offset] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Every point touching this one, diagonals included.
+pub const ALL_AROUND: &[Offset] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+
+    /// The point `offset` away, unless that would go below zero on either axis.
+    pub fn offset(self, (dx, dy): Offset) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row, top to bottom.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Build a grid from `lines` of puzzle input, one cell per character.
+    /// `cell` turns each character into a cell, or gives `None` if it doesn't belong,
+    /// in which case the error says `expected` should have been there instead.
+    /// Every line has to be as long as the first.
+    pub fn parse(
+        day: u8,
+        lines: &[&str],
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, ParseError> {
+        let Some(first) = lines.first() else {
+            return Err(parse::error_at_end_of_input(day, "", "at least one row"));
+        };
+        let width = first.chars().count();
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (index, &text) in lines.iter().enumerate() {
+            let line = Line { day, index, text };
+            let mut row_width = 0;
+
+            for (i, char) in text.char_indices() {
+                let found = &text[i..i + char.len_utf8()];
+                if row_width == width {
+                    return Err(
+                        line.error(&text[i..], format!("the row to end after {} cells", width))
+                    );
+                }
+                cells.push(cell(char).ok_or_else(|| line.error(found, expected))?);
+                row_width += 1;
+            }
+
+            if row_width < width {
+                return Err(line.error_at_end(format!("{} more cell(s)", width - row_width)));
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * self.width + point.x])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    /// Every cell alongside its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The points `stencil` reaches from `point` that are still inside the grid.
+    pub fn neighbours<'a>(
+        &self,
+        point: Point,
+        stencil: &'a [Offset],
+    ) -> impl Iterator<Item = Point> + use<'a, T> {
+        let (width, height) = (self.width, self.height);
+
+        stencil
+            .iter()
+            .filter_map(move |&offset| point.offset(offset))
+            .filter(move |p| p.x < width && p.y < height)
+    }
+
+    /// The up to 4 points directly above, below, left and right of `point`.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + use<T> {
+        self.neighbours(point, ORTHOGONAL)
+    }
+
+    /// The up to 8 points around `point`, diagonals included.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + use<T> {
+        self.neighbours(point, ALL_AROUND)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} is outside a grid {} wide",
+            x,
+            self.width
+        );
+
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside a {}x{} grid", point, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside a {}x{} grid", point, width, height))
+    }
+}
+
+/// Draws the grid one row per line, the way it looked in the puzzle input.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A grid serializes as its rows, top to bottom.
+impl<T: Serialize> Serialize for Grid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(lines: &[&str]) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(0, lines, |c| c.to_digit(10), "a digit")
+    }
+
+    #[test]
+    fn rows_columns_and_rendering() {
+        let grid = digits(&["123", "456"]).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid[Point::new(0, 1)], 4);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(serde_json::to_string(&grid).unwrap(), "[[1,2,3],[4,5,6]]");
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = digits(&["123", "456", "789"]).unwrap();
+        let around = |p, stencil| {
+            grid.neighbours(p, stencil)
+                .map(|p| grid[p])
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(around(Point::new(0, 0), ORTHOGONAL), vec![2, 4]);
+        assert_eq!(around(Point::new(0, 0), ALL_AROUND), vec![2, 4, 5]);
+        assert_eq!(around(Point::new(1, 1), ALL_AROUND).len(), 8);
+        assert_eq!(around(Point::new(2, 2), &[(1, 0), (-2, -2)]), vec![1]);
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let err = digits(&["123", "45"]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = digits(&["12", "345"]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        let err = digits(&["12", "3x"]).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow, bail};
+use tracing::warn;
+
+use crate::fetch::{self, FetchConfig};
+
+/// Environment variable naming a directory that holds the `dayNN.input.txt` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Name of the directory we look for when walking up from the executable or the CWD.
+pub const INPUT_DIR_NAME: &str = "input_files";
+
+pub fn file_name(day: u8) -> String {
+    format!("day{:02}.input.txt", day)
+}
+
+/// Where a day's puzzle input ended up coming from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)?;
+                Ok(buf)
+            }
+            InputSource::File(path) => Ok(fs::read_to_string(path)?),
+        }
+    }
+}
+
+/// Every directory that might hold the input files, in the order they should be tried:
+/// `$AOC_INPUT_DIR`, then any `input_files/` above the executable, then above the CWD.
+pub fn candidate_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        dirs.push(PathBuf::from(dir));
+    }
+
+    let exe_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let cwd = env::current_dir().ok();
+
+    for start in exe_dir.iter().chain(cwd.iter()) {
+        for ancestor in start.ancestors() {
+            let dir = ancestor.join(INPUT_DIR_NAME);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Find the input for `day`. An explicit `arg` always wins: `-` means stdin,
+/// anything else is a file path. Without one, the candidate directories are searched.
+pub fn locate(day: u8, arg: Option<&Path>) -> Result<InputSource> {
+    if let Some(path) = arg {
+        if path == Path::new("-") {
+            return Ok(InputSource::Stdin);
+        }
+        if path.is_file() {
+            return Ok(InputSource::File(path.to_path_buf()));
+        }
+        bail!("Input file {} does not exist", path.display());
+    }
+
+    let mut tried = Vec::new();
+
+    if env::var_os(INPUT_DIR_VAR).is_none() {
+        tried.push(format!("${} (not set)", INPUT_DIR_VAR));
+    }
+
+    for dir in candidate_dirs() {
+        let path = dir.join(file_name(day));
+        if path.is_file() {
+            return Ok(InputSource::File(path));
+        }
+        tried.push(path.display().to_string());
+    }
+
+    bail!(
+        "Could not find the input for day {:02}. Tried:\n  {}",
+        day,
+        tried.join("\n  ")
+    )
+}
+
+/// [`locate`] the input for `day`. If it can't be found anywhere, and wasn't asked for
+/// by name, try downloading it into the cache, warning that it did.
+pub fn find(day: u8, arg: Option<&Path>) -> Result<InputSource> {
+    match locate(day, arg) {
+        Ok(source) => Ok(source),
+        Err(not_found) if arg.is_none() => {
+            let config = FetchConfig::from_env();
+
+            match fetch::fetch(&config, day) {
+                Ok(path) => {
+                    warn!(
+                        "Downloaded the input for day {:02} to {}",
+                        day,
+                        path.display()
+                    );
+                    Ok(InputSource::File(path))
+                }
+                Err(e) => Err(anyhow!(
+                    "{:#}\nDownloading it didn't work either: {:#}",
+                    not_found,
+                    e
+                )),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// [`find`] the input for `day` and read it all in.
+pub fn read(day: u8, arg: Option<&Path>) -> Result<String> {
+    find(day, arg)?.read()
+}
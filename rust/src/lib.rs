@@ -0,0 +1,387 @@
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use serde::Serialize;
+
+use answers::{AnswerDb, Verdict};
+use output::{AnswerRecord, Format};
+use stream::{LineSource, Streamed, StreamedParts};
+use viz::Animation;
+
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod fetch;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod logging;
+pub mod output;
+pub mod parallel;
+pub mod parity;
+pub mod parse;
+pub mod scaffold;
+pub mod stream;
+pub mod viz;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+
+/// One day's puzzle: parse the input once, then answer both parts from the parsed form.
+pub trait Solution {
+    const DAY: u8;
+
+    /// The parsed input. It serializes so that other languages can see it as plain data.
+    type Input: Serialize;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Solve straight from the input as it's read, a line at a time, for inputs too big to
+    /// read in whole. Days that can work that way override this. An error means the input
+    /// couldn't be read or parsed; the parts succeed or fail on their own.
+    fn stream(_lines: &mut LineSource) -> Result<StreamedParts<Self::Answer1, Self::Answer2>> {
+        Ok((Streamed::NeedsWholeInput, Streamed::NeedsWholeInput))
+    }
+
+    /// Watch the puzzle being solved a step at a time, for days that simulate something
+    /// worth watching. Days that do override this.
+    fn animate(_input: &Self::Input) -> Option<Box<dyn Animation + '_>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A [`Solution`] with its types erased, so days can be picked by number at runtime.
+pub trait Day {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>>;
+    fn stream(&self, lines: &mut LineSource) -> Result<[Streamed<String>; 2]>;
+}
+
+/// Parsed input for some day, ready to have either part run against it.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<String>;
+    fn animate(&self) -> Option<Box<dyn Animation + '_>>;
+
+    /// The parsed input as plain JSON-shaped data.
+    fn to_value(&self) -> Result<serde_json::Value>;
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Result<String> {
+        let _span = tracing::debug_span!("solve", day = S::DAY, part = part.number()).entered();
+
+        Ok(match part {
+            Part::One => S::part1(&self.0)?.to_string(),
+            Part::Two => S::part2(&self.0)?.to_string(),
+        })
+    }
+
+    fn animate(&self) -> Option<Box<dyn Animation + '_>> {
+        S::animate(&self.0)
+    }
+
+    fn to_value(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(&self.0)?)
+    }
+}
+
+impl<S: Solution + 'static> Day for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>> {
+        let _span = tracing::debug_span!("parse", day = S::DAY).entered();
+
+        Ok(Box::new(ParsedInput::<S>(S::parse(input)?)))
+    }
+
+    fn stream(&self, lines: &mut LineSource) -> Result<[Streamed<String>; 2]> {
+        let _span = tracing::debug_span!("stream", day = S::DAY).entered();
+        let (part1, part2) = S::stream(lines)?;
+
+        Ok([part1.map(|a| a.to_string()), part2.map(|a| a.to_string())])
+    }
+}
+
+pub const DAYS: &[&dyn Day] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+pub fn get_day(day: u8) -> Option<&'static dyn Day> {
+    DAYS.iter().copied().find(|d| d.day() == day)
+}
+
+/// Parse `input` as the given day's puzzle and solve one part of it.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<String> {
+    let day = get_day(day).ok_or_else(|| anyhow!("No solution for day {}", day))?;
+
+    day.parse(input)?.solve(part)
+}
+
+/// Command line accepted by every `dayNN` binary.
+#[derive(Parser)]
+struct DayArgs {
+    /// Puzzle input file, or `-` for stdin. Searched for when omitted.
+    input: Option<PathBuf>,
+
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Save any new answers as confirmed.
+    #[arg(long)]
+    record: bool,
+
+    /// Confirmed answers to check against. Found next to the inputs by default.
+    #[arg(long, value_name = "ANSWERS_TOML")]
+    answers: Option<PathBuf>,
+
+    /// Solve while reading the input a line at a time, rather than reading it all in first,
+    /// for inputs too big to fit in memory. Only some days can.
+    #[arg(long)]
+    stream: bool,
+
+    /// Report how much each phase allocates, instead of timing it.
+    /// Needs the `profile-alloc` feature.
+    #[arg(long, conflicts_with = "stream")]
+    profile_alloc: bool,
+
+    #[command(flatten)]
+    verbosity: logging::Verbosity,
+}
+
+/// Check `answers` against the ones on record, saving any new ones if asked to.
+fn check_answers(
+    day: u8,
+    args: &DayArgs,
+    input_hash: &str,
+    answers: &[(Part, &str)],
+) -> Result<Vec<Verdict>> {
+    let Some(mut db) = AnswerDb::open(args.answers.clone())? else {
+        return Ok(Vec::new());
+    };
+
+    let verdicts = db.verify(day, input_hash, answers, args.record);
+    if args.record {
+        db.save()?;
+    }
+
+    Ok(verdicts)
+}
+
+fn bail_on_mismatch(day: u8, verdicts: &[Verdict]) -> Result<()> {
+    if verdicts
+        .iter()
+        .any(|v| matches!(v, Verdict::Mismatch { .. }))
+    {
+        bail!(
+            "Day {:02} gave an answer that doesn't match the one on record",
+            day
+        );
+    }
+
+    Ok(())
+}
+
+/// Entry point for the `dayNN` binaries: solve both parts, report how long each step took,
+/// and check the answers against the ones on record.
+pub fn run(day: &dyn Day) -> Result<()> {
+    let args = DayArgs::parse();
+    args.verbosity.init();
+    if args.stream {
+        return run_streaming(day, &args);
+    }
+    if args.profile_alloc {
+        return run_profiling(day, &args);
+    }
+
+    let input = input::read(day.day(), args.input.as_deref())?;
+    let input_hash = output::input_hash(&input);
+
+    // A part that fails doesn't stop the other being solved and reported.
+    let measurement = bench::measure(day, &input, 1)?;
+    let parts = Part::BOTH.into_iter().zip(&measurement.answers);
+
+    let answered = parts
+        .clone()
+        .filter_map(|(part, answer)| Some((part, answer.as_ref().ok()?.as_str())))
+        .collect::<Vec<_>>();
+    let verdicts = check_answers(day.day(), &args, &input_hash, &answered)?;
+
+    if args.format != Format::Text {
+        let records = AnswerRecord::from_measurement(&measurement, &input_hash);
+        output::print_records(&records, args.format)?;
+    } else {
+        let timings = &measurement.timings;
+        let secs = |stats: &bench::Stats| stats.median_ms / 1000.0;
+
+        println!("Data parsed in {} seconds", secs(&timings.parse));
+        let mut verdicts = verdicts.iter();
+        for ((part, answer), solve) in parts.clone().zip([&timings.part1, &timings.part2]) {
+            match answer {
+                Ok(answer) => println!(
+                    "Part {} result: {}, took {} seconds{}",
+                    part.number(),
+                    answer,
+                    secs(solve),
+                    verdicts
+                        .next()
+                        .map(|v| format!(" [{}]", v))
+                        .unwrap_or_default()
+                ),
+                Err(e) => println!("Part {} failed: {:#}", part.number(), e),
+            }
+        }
+    }
+
+    if let Some((part, Err(e))) = parts.clone().find(|(_, answer)| answer.is_err()) {
+        bail!(
+            "Day {:02} part {} failed: {:#}",
+            day.day(),
+            part.number(),
+            e
+        );
+    }
+
+    bail_on_mismatch(day.day(), &verdicts)
+}
+
+/// [`run`] with `--profile-alloc`.
+fn run_profiling(day: &dyn Day, args: &DayArgs) -> Result<()> {
+    let input = input::read(day.day(), args.input.as_deref())?;
+
+    let mut failed = None;
+    for (phase, stats) in alloc::profile(day, &input)? {
+        match stats {
+            Ok(stats) => println!(
+                "{}: peak heap {}, {} allocations, {} allocated",
+                phase.name(),
+                alloc::format_bytes(stats.peak_bytes),
+                stats.allocations,
+                alloc::format_bytes(stats.bytes_allocated)
+            ),
+            Err(e) => {
+                println!("{} failed: {:#}", phase.name(), e);
+                failed.get_or_insert((phase, e));
+            }
+        }
+    }
+
+    if let Some((phase, e)) = failed {
+        bail!("Day {:02} {} failed: {:#}", day.day(), phase.name(), e);
+    }
+
+    Ok(())
+}
+
+/// [`run`] with `--stream`: there's no separate parse to time, since parsing and solving
+/// happen together as the lines go by.
+fn run_streaming(day: &dyn Day, args: &DayArgs) -> Result<()> {
+    let source = input::find(day.day(), args.input.as_deref())?;
+    let report = stream::solve(day, LineSource::open(day.day(), &source)?)?;
+
+    let answered = Part::BOTH
+        .into_iter()
+        .zip(&report.parts)
+        .filter_map(|(part, streamed)| match streamed {
+            Streamed::Answer(answer) => Some((part, answer.as_str())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let verdicts = check_answers(day.day(), args, &report.input_hash, &answered)?;
+
+    if args.format != Format::Text {
+        let records = answered
+            .iter()
+            .map(|&(part, answer)| AnswerRecord {
+                day: day.day(),
+                part: part.number(),
+                answer: answer.to_string(),
+                parse_ms: 0.0,
+                solve_ms: report.elapsed.as_secs_f64() * 1000.0,
+                input_hash: report.input_hash.clone(),
+            })
+            .collect::<Vec<_>>();
+        output::print_records(&records, args.format)?;
+    } else {
+        println!(
+            "Streamed and solved in {} seconds",
+            report.elapsed.as_secs_f64()
+        );
+
+        let mut verdicts = verdicts.iter();
+        for (part, streamed) in Part::BOTH.into_iter().zip(&report.parts) {
+            match streamed {
+                Streamed::Answer(answer) => println!(
+                    "Part {} result: {}{}",
+                    part.number(),
+                    answer,
+                    verdicts
+                        .next()
+                        .map(|v| format!(" [{}]", v))
+                        .unwrap_or_default()
+                ),
+                Streamed::Failed(e) => println!("Part {} failed: {:#}", part.number(), e),
+                Streamed::NeedsWholeInput => println!(
+                    "Part {} needs the whole input at once; run without --stream for it",
+                    part.number()
+                ),
+            }
+        }
+    }
+
+    if let Some((i, Streamed::Failed(e))) = report
+        .parts
+        .iter()
+        .enumerate()
+        .find(|(_, streamed)| matches!(streamed, Streamed::Failed(_)))
+    {
+        bail!("Day {:02} part {} failed: {:#}", day.day(), i + 1, e);
+    }
+
+    bail_on_mismatch(day.day(), &verdicts)
+}
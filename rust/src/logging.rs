@@ -0,0 +1,36 @@
+//! How much the days say about what they're doing while they solve, for working out where a
+//! wrong answer went wrong without editing any source.
+
+use std::io::{self, IsTerminal};
+
+use clap::{ArgAction, Args};
+use tracing::level_filters::LevelFilter;
+
+/// The `-v` flag shared by every binary.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct Verbosity {
+    /// Show what the solvers are doing on stderr: `-v` for each step, `-vv` for every detail.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+impl Verbosity {
+    pub fn level(&self) -> LevelFilter {
+        match self.verbose {
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+
+    /// Send the days' events to stderr, so they never get mixed up with the answers.
+    /// Only the first call in a process has any effect.
+    pub fn init(&self) {
+        let _ = tracing_subscriber::fmt()
+            .with_max_level(self.level())
+            .with_writer(io::stderr)
+            .with_ansi(io::stderr().is_terminal())
+            .without_time()
+            .try_init();
+    }
+}
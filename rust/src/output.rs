@@ -0,0 +1,87 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{Part, bench::Measurement};
+
+/// How the binaries print their answers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// A single JSON array of answer records.
+    Json,
+    /// One JSON answer record per line.
+    Ndjson,
+}
+
+/// One answer, in the shape every day reports it in machine-readable output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnswerRecord {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+    /// Hex SHA-256 of the exact input text, so answers can be matched to inputs.
+    pub input_hash: String,
+}
+
+impl AnswerRecord {
+    /// The answers from a single-run [`Measurement`], for whichever parts were solved.
+    pub fn from_measurement(measurement: &Measurement, input_hash: &str) -> Vec<AnswerRecord> {
+        let timings = &measurement.timings;
+
+        [(Part::One, &timings.part1), (Part::Two, &timings.part2)]
+            .into_iter()
+            .filter_map(|(part, solve)| {
+                Some(AnswerRecord {
+                    day: timings.day,
+                    part: part.number(),
+                    answer: measurement.answers[part as usize].as_ref().ok()?.clone(),
+                    parse_ms: timings.parse.median_ms,
+                    solve_ms: solve.median_ms,
+                    input_hash: input_hash.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Print `records` as JSON or NDJSON. Text output is up to the caller,
+/// since what makes sense there depends on what's being run.
+pub fn print_records(records: &[AnswerRecord], format: Format) -> Result<()> {
+    match format {
+        Format::Text => {}
+        Format::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        Format::Ndjson => {
+            for record in records {
+                println!("{}", serde_json::to_string(record)?);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_hash_is_hex_sha256() {
+        assert_eq!(
+            input_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Fanning out independent pieces of work, like the banks in day 3 or the machines in day 10.
+//!
+//! With the `parallel` feature these run on rayon's thread pool, otherwise one after another.
+//! Either way the results come back in the original order and errors are picked the same way,
+//! so the answers don't depend on which one you built.
+
+use anyhow::Result;
+
+use crate::answer::Answer;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// `f` applied to each item, in order.
+#[cfg(feature = "parallel")]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    items.par_iter().map(f).collect()
+}
+
+/// `f` applied to each item, in order.
+#[cfg(not(feature = "parallel"))]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+/// Add up `f` of every item. If any of them fail, the error is the one from the earliest item,
+/// however the work happened to be scheduled.
+pub fn try_sum<T: Sync, A: Into<Answer> + Send>(
+    items: &[T],
+    f: impl Fn(&T) -> Result<A> + Sync + Send,
+) -> Result<Answer> {
+    map(items, f)
+        .into_iter()
+        .map(|value| value.map(Into::into))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_reports_the_first_error() {
+        let items = (0..1000).collect::<Vec<u64>>();
+        assert_eq!(
+            map(&items, |x| x * 2),
+            items.iter().map(|x| x * 2).collect::<Vec<_>>()
+        );
+        assert_eq!(try_sum(&items, |&x| Ok(x)).unwrap(), 499_500);
+        assert_eq!(
+            try_sum(&[u64::MAX, 1], |&x| Ok(x)).unwrap(),
+            u64::MAX as i128 + 1
+        );
+
+        let err = try_sum(&items, |&x| {
+            if x % 300 == 299 {
+                bail!("item {}", x)
+            } else {
+                Ok(x)
+            }
+        });
+        assert_eq!(err.unwrap_err().to_string(), "item 299");
+    }
+}
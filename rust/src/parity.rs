@@ -0,0 +1,274 @@
+//! Checking the Python solutions in `python/` against the Rust ones, on the same inputs.
+//!
+//! Each Python day runs in one long-lived interpreter that reads its input through
+//! `common.load_input` and answers both parts, the same as `dayNN.py` would on its own,
+//! so inputs can be thrown at it quickly enough to shrink a disagreement down to size.
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Deserialize;
+
+use crate::{DAYS, Day, Part};
+
+/// Environment variable naming the directory that holds `common.py` and the `dayNN.py` files.
+pub const PYTHON_DIR_VAR: &str = "AOC_PYTHON_DIR";
+
+/// Name of the directory we look for when walking up from the executable or the CWD.
+pub const PYTHON_DIR_NAME: &str = "python";
+
+/// Loads one day's module, then answers each input file named on stdin with a line of JSON.
+/// Each part gets its own fresh copy of the input, in case it changes it.
+const DRIVER: &str = r#"
+import importlib, json, sys
+
+sys.path.insert(0, sys.argv[1])
+from common import load_input
+day = importlib.import_module(sys.argv[2])
+
+for request in sys.stdin:
+    answers = []
+    for part in (day.part1, day.part2):
+        try:
+            answers.append({"answer": str(part(load_input(json.loads(request))))})
+        except Exception as e:
+            answers.append({"error": f"{type(e).__name__}: {e}"})
+    print(json.dumps(answers), flush=True)
+"#;
+
+/// Find the Python solutions: `$AOC_PYTHON_DIR`, then any `python/` holding a `common.py`
+/// above the executable or the CWD.
+pub fn find_python_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os(PYTHON_DIR_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let exe_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let cwd = env::current_dir().ok();
+
+    exe_dir
+        .iter()
+        .chain(cwd.iter())
+        .flat_map(|start| start.ancestors())
+        .map(|ancestor| ancestor.join(PYTHON_DIR_NAME))
+        .find(|dir| dir.join("common.py").is_file())
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not find the Python solutions; set ${} to their directory",
+                PYTHON_DIR_VAR
+            )
+        })
+}
+
+/// The days solved in both Rust and Python.
+pub fn shared_days(python_dir: &Path) -> Vec<&'static dyn Day> {
+    DAYS.iter()
+        .copied()
+        .filter(|day| python_dir.join(format!("day{:02}.py", day.day())).is_file())
+        .collect()
+}
+
+/// What one implementation made of one part.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartResult {
+    Answer(String),
+    Error(String),
+}
+
+impl PartResult {
+    /// The same answer, or both giving up on the input, however differently they put it.
+    pub fn agrees_with(&self, other: &PartResult) -> bool {
+        match (self, other) {
+            (PartResult::Answer(a), PartResult::Answer(b)) => a == b,
+            (PartResult::Error(_), PartResult::Error(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Both implementations' results for both parts of one input.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub rust: [PartResult; 2],
+    pub python: [PartResult; 2],
+}
+
+impl Comparison {
+    pub fn diverging_parts(&self) -> Vec<Part> {
+        Part::BOTH
+            .into_iter()
+            .zip(self.rust.iter().zip(&self.python))
+            .filter(|(_, (rust, python))| !rust.agrees_with(python))
+            .map(|(part, _)| part)
+            .collect()
+    }
+}
+
+/// One day's Python solution, running in the background and waiting for inputs.
+pub struct PythonSolver {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// Where each input is written for `load_input` to read back.
+    scratch: PathBuf,
+}
+
+impl PythonSolver {
+    pub fn start(python: &str, python_dir: &Path, day: u8) -> Result<PythonSolver> {
+        let mut child = Command::new(python)
+            .arg("-c")
+            .arg(DRIVER)
+            .arg(python_dir)
+            .arg(format!("day{:02}", day))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Could not run {}", python))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("No stdin for {}", python))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("No stdout for {}", python))?;
+        let scratch = env::temp_dir().join(format!(
+            "aoc-parity-{}-day{:02}.txt",
+            std::process::id(),
+            day
+        ));
+
+        Ok(PythonSolver {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            scratch,
+        })
+    }
+
+    pub fn solve(&mut self, input: &str) -> Result<[PartResult; 2]> {
+        fs::write(&self.scratch, input)
+            .with_context(|| format!("Could not write {}", self.scratch.display()))?;
+
+        let path = self
+            .scratch
+            .to_str()
+            .ok_or_else(|| anyhow!("Temporary path isn't UTF-8"))?;
+        writeln!(self.stdin, "{}", serde_json::to_string(path)?)?;
+        self.stdin.flush()?;
+
+        let mut reply = String::new();
+        if self.stdout.read_line(&mut reply)? == 0 {
+            bail!("Python stopped before answering; see its output above");
+        }
+
+        Ok(serde_json::from_str(&reply)?)
+    }
+}
+
+impl Drop for PythonSolver {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_file(&self.scratch);
+    }
+}
+
+/// The Rust answers for one input, as strings, the same way `aoc run` gives them.
+pub fn solve_rust(day: &dyn Day, input: &str) -> [PartResult; 2] {
+    let result = |solved: Result<String>| match solved {
+        Ok(answer) => PartResult::Answer(answer),
+        Err(e) => PartResult::Error(format!("{:#}", e)),
+    };
+
+    match day.parse(input) {
+        Ok(parsed) => Part::BOTH.map(|part| result(parsed.solve(part))),
+        Err(e) => [
+            PartResult::Error(format!("{:#}", e)),
+            PartResult::Error(format!("{:#}", e)),
+        ],
+    }
+}
+
+pub fn compare(day: &dyn Day, python: &mut PythonSolver, input: &str) -> Result<Comparison> {
+    Ok(Comparison {
+        rust: solve_rust(day, input),
+        python: python.solve(input)?,
+    })
+}
+
+/// Take lines out of `input` for as long as `still_fails` holds, first in big chunks,
+/// then smaller ones, down to single lines. What's left can't lose any one line and
+/// still fail.
+pub fn shrink(input: &str, mut still_fails: impl FnMut(&str) -> Result<bool>) -> Result<String> {
+    let mut lines = input.split_inclusive('\n').collect::<Vec<_>>();
+    let mut chunk = lines.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut removed_any = false;
+        let mut start = 0;
+
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate = [&lines[..start], &lines[end..]].concat();
+
+            if still_fails(&candidate.concat())? {
+                lines = candidate;
+                removed_any = true;
+            } else {
+                start = end;
+            }
+        }
+
+        // Single lines keep going until none can go; bigger chunks only get one pass each.
+        if chunk > 1 {
+            chunk /= 2;
+        } else if !removed_any {
+            break;
+        }
+    }
+
+    Ok(lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_the_lines_that_matter() {
+        let input = (1..=40).map(|i| format!("{}\n", i)).collect::<String>();
+        let shrunk = shrink(&input, |candidate| {
+            let lines = candidate.lines().collect::<Vec<_>>();
+            Ok(lines.contains(&"7") && lines.contains(&"31"))
+        })
+        .unwrap();
+
+        assert_eq!(shrunk, "7\n31\n");
+    }
+
+    #[test]
+    fn both_failing_counts_as_agreeing() {
+        let comparison = Comparison {
+            rust: [
+                PartResult::Error("bad".into()),
+                PartResult::Answer("3".into()),
+            ],
+            python: [
+                PartResult::Error("ValueError".into()),
+                PartResult::Answer("4".into()),
+            ],
+        };
+
+        assert_eq!(comparison.diverging_parts(), vec![Part::Two]);
+    }
+}
@@ -0,0 +1,147 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// A problem with the puzzle input, pinned to the line and column it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole line the problem is on.
+    pub source_line: String,
+    /// The part of the line that was wrong. Empty if something was missing instead.
+    pub found: String,
+    /// What should have been there, e.g. "a number".
+    pub expected: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if !self.found.is_empty() {
+            write!(f, ", found {:?}", self.found)?;
+        }
+
+        let gutter = self.line.to_string().len();
+        let carets = self.found.chars().count().max(1);
+
+        writeln!(f)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {}{}",
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(carets)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of puzzle input, with enough context to point at problems in it.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    /// 0-based index of the line within the input.
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `found`, which must be a slice of this line's text.
+    pub fn error(&self, found: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (found.as_ptr() as usize).saturating_sub(self.text.as_ptr() as usize);
+
+        self.error_at(offset.min(self.text.len()), found, expected)
+    }
+
+    /// An error for something missing from the end of this line.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.text.len(), "", expected)
+    }
+
+    fn error_at(&self, offset: usize, found: &str, expected: impl Into<String>) -> ParseError {
+        // Byte offsets may land inside a multi-byte character, so count whole characters up to it.
+        let column = self
+            .text
+            .char_indices()
+            .take_while(|&(i, _)| i < offset)
+            .count()
+            + 1;
+
+        ParseError {
+            day: self.day,
+            line: self.index + 1,
+            column,
+            source_line: self.text.to_string(),
+            found: found.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Parse `field`, a slice of this line, as a number.
+    pub fn number<T: FromStr>(&self, field: &str) -> Result<T, ParseError> {
+        field
+            .parse::<T>()
+            .map_err(|_| self.error(field, "a number"))
+    }
+}
+
+/// The lines of `input`, tagged with where they came from. CRLF line endings are fine,
+/// including a stray `\r` left on the last line when the input was cut short.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(index, text)| Line {
+        day,
+        index,
+        text: text.strip_suffix('\r').unwrap_or(text),
+    })
+}
+
+/// An error for something missing after the last line of `input`.
+pub fn error_at_end_of_input(day: u8, input: &str, expected: impl Into<String>) -> ParseError {
+    Line {
+        day,
+        index: input.lines().count(),
+        text: "",
+    }
+    .error_at_end(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_points_at_the_problem() {
+        let line = Line {
+            day: 9,
+            index: 11,
+            text: "7,one",
+        };
+        let err = line.number::<u64>(&line.text[2..]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "day 09, line 12, column 3: expected a number, found \"one\"\n   |\n12 | 7,one\n   |   ^^^"
+        );
+    }
+
+    #[test]
+    fn columns_count_characters_not_bytes() {
+        let line = Line {
+            day: 1,
+            index: 0,
+            text: "ééx",
+        };
+        let err = line.error(&line.text[4..], "nothing");
+
+        assert_eq!(err.column, 3);
+    }
+}
@@ -0,0 +1,136 @@
+//! Sets up a new day: its solution module, its binary, and everywhere they need registering.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const BIN_TEMPLATE: &str = include_str!("../templates/bin.rs.tmpl");
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{N}}", &day.to_string())
+}
+
+/// Put `new_line` among the run of lines matching `is_entry`, keeping them sorted.
+/// The entries are assumed to already be in order, as they are in `lib.rs`.
+fn insert_sorted(text: &str, new_line: &str, is_entry: impl Fn(&str) -> bool) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<usize> = (0..lines.len()).filter(|&i| is_entry(lines[i])).collect();
+    let last = *entries.last()?;
+    let at = entries
+        .iter()
+        .copied()
+        .find(|&i| lines[i] > new_line)
+        .unwrap_or(last + 1);
+
+    let mut result: Vec<&str> = lines[..at].to_vec();
+    result.push(new_line);
+    result.extend(&lines[at..]);
+
+    Some(result.join("\n") + "\n")
+}
+
+fn register_module(lib_rs: &str, day: u8) -> Result<String> {
+    let with_mod = insert_sorted(lib_rs, &format!("pub mod day{:02};", day), |line| {
+        line.starts_with("pub mod day")
+    })
+    .context("lib.rs has no `pub mod dayNN;` lines to add to")?;
+
+    insert_sorted(
+        &with_mod,
+        &format!("    &day{0:02}::Day{0:02},", day),
+        |line| line.starts_with("    &day"),
+    )
+    .context("lib.rs has no DAYS entries to add to")
+}
+
+/// Add a `[[bin]]` block for the day, just before the first day that comes after it.
+fn register_bin(cargo_toml: &str, day: u8) -> String {
+    let name = format!("day{:02}", day);
+    let block = format!("[[bin]]\nname = \"{0}\"\npath = \"src/bin/{0}.rs\"\n", name);
+
+    let later_bin = cargo_toml
+        .match_indices("[[bin]]\nname = \"day")
+        .find(|&(at, _)| {
+            cargo_toml[at + "[[bin]]\nname = \"".len()..]
+                .split('"')
+                .next()
+                .is_some_and(|other| other > name.as_str())
+        });
+
+    match later_bin {
+        Some((at, _)) => format!("{}{}\n{}", &cargo_toml[..at], block, &cargo_toml[at..]),
+        None => format!("{}\n\n{}", cargo_toml.trim_end(), block),
+    }
+}
+
+/// Create and register `day` in the crate at `crate_dir`. Nothing is written if any part
+/// of the day already exists. Gives the files that were created.
+pub fn new_day(crate_dir: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let name = format!("day{:02}", day);
+    let module_path = crate_dir.join("src").join(format!("{}.rs", name));
+    let bin_path = crate_dir
+        .join("src")
+        .join("bin")
+        .join(format!("{}.rs", name));
+    let lib_path = crate_dir.join("src").join("lib.rs");
+    let cargo_path = crate_dir.join("Cargo.toml");
+
+    let lib_rs = fs::read_to_string(&lib_path)
+        .with_context(|| format!("Could not read {}", lib_path.display()))?;
+    let cargo_toml = fs::read_to_string(&cargo_path)
+        .with_context(|| format!("Could not read {}", cargo_path.display()))?;
+
+    for path in [&module_path, &bin_path] {
+        if path.exists() {
+            bail!("{} already exists; not overwriting it", path.display());
+        }
+    }
+    if lib_rs.contains(&format!("pub mod {};", name)) {
+        bail!("{} is already declared in {}", name, lib_path.display());
+    }
+    if cargo_toml.contains(&format!("name = \"{}\"", name)) {
+        bail!("{} already has a [[bin]] in {}", name, cargo_path.display());
+    }
+
+    let lib_rs = register_module(&lib_rs, day)?;
+    let cargo_toml = register_bin(&cargo_toml, day);
+    toml::from_str::<toml::Table>(&cargo_toml).context("Cargo.toml would stop being valid")?;
+
+    fs::write(&module_path, fill(DAY_TEMPLATE, day))?;
+    fs::write(&bin_path, fill(BIN_TEMPLATE, day))?;
+    fs::write(&lib_path, lib_rs)?;
+    fs::write(&cargo_path, cargo_toml)?;
+
+    Ok(vec![module_path, bin_path])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_in_day_order() {
+        let lib_rs = "pub mod parse;\n\npub mod day01;\npub mod day03;\n\n\
+                      pub const DAYS: &[&dyn Day] = &[\n    &day01::Day01,\n    &day03::Day03,\n];\n";
+        let cargo_toml = "[[bin]]\nname = \"day01\"\npath = \"src/bin/day01.rs\"\n\n\
+                          [[bin]]\nname = \"day03\"\npath = \"src/bin/day03.rs\"\n";
+
+        assert_eq!(
+            register_module(lib_rs, 2).unwrap(),
+            "pub mod parse;\n\npub mod day01;\npub mod day02;\npub mod day03;\n\n\
+             pub const DAYS: &[&dyn Day] = &[\n    &day01::Day01,\n    &day02::Day02,\n    &day03::Day03,\n];\n"
+        );
+        assert_eq!(
+            register_bin(cargo_toml, 2),
+            "[[bin]]\nname = \"day01\"\npath = \"src/bin/day01.rs\"\n\n\
+             [[bin]]\nname = \"day02\"\npath = \"src/bin/day02.rs\"\n\n\
+             [[bin]]\nname = \"day03\"\npath = \"src/bin/day03.rs\"\n"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Reading puzzle input a line at a time, for days that can be solved without ever holding
+//! the whole input in memory. Only one line is kept at a time, however big the input gets.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, Cursor},
+    path::Path,
+    str,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use memmap2::Mmap;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Day,
+    input::InputSource,
+    parse::{Line, ParseError},
+};
+
+/// Puzzle input coming in a line at a time, from a file, stdin or any other reader.
+pub struct LineSource {
+    day: u8,
+    reader: Box<dyn BufRead>,
+    /// The line most recently read, line ending and all.
+    buf: Vec<u8>,
+    lines_read: usize,
+    /// Hash of everything read so far, so answers can still be matched to their input.
+    hasher: Sha256,
+}
+
+impl LineSource {
+    pub fn new(day: u8, reader: impl BufRead + 'static) -> LineSource {
+        LineSource {
+            day,
+            reader: Box::new(reader),
+            buf: Vec::new(),
+            lines_read: 0,
+            hasher: Sha256::new(),
+        }
+    }
+
+    pub fn stdin(day: u8) -> LineSource {
+        LineSource::new(day, io::stdin().lock())
+    }
+
+    /// Read the file at `path` through a memory map, leaving the OS to page it in and out
+    /// as the lines go by.
+    pub fn mmap(day: u8, path: &Path) -> Result<LineSource> {
+        let file =
+            File::open(path).with_context(|| format!("Could not open {}", path.display()))?;
+        // SAFETY: the map is only ever read, and an input file changing under us while we
+        // solve it would be just as wrong, if less exciting, with ordinary reads.
+        let map = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Could not map {}", path.display()))?;
+        #[cfg(unix)]
+        map.advise(memmap2::Advice::Sequential)?;
+
+        Ok(LineSource::new(day, Cursor::new(map)))
+    }
+
+    pub fn open(day: u8, source: &InputSource) -> Result<LineSource> {
+        match source {
+            InputSource::Stdin => Ok(LineSource::stdin(day)),
+            InputSource::File(path) => LineSource::mmap(day, path),
+        }
+    }
+
+    /// The next line, without its line ending, or `None` once the input runs out.
+    /// Lines are split exactly the way [`crate::parse::lines`] splits them.
+    pub fn next_line(&mut self) -> Result<Option<Line<'_>>> {
+        self.buf.clear();
+        if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+            return Ok(None);
+        }
+        self.hasher.update(&self.buf);
+
+        let index = self.lines_read;
+        self.lines_read += 1;
+
+        let mut text = str::from_utf8(&self.buf).map_err(|e| {
+            anyhow!(
+                "day {:02}, line {}: not valid UTF-8: {}",
+                self.day,
+                index + 1,
+                e
+            )
+        })?;
+        // `str::lines` takes a `\r\n` as one line ending, but a lone `\r` at the very end
+        // is left alone...
+        if let Some(line) = text.strip_suffix('\n') {
+            text = line.strip_suffix('\r').unwrap_or(line);
+        }
+        // ...and `parse::lines` strips one more `\r` from whatever's left.
+        text = text.strip_suffix('\r').unwrap_or(text);
+
+        Ok(Some(Line {
+            day: self.day,
+            index,
+            text,
+        }))
+    }
+
+    /// An error for something missing after the last line read.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        Line {
+            day: self.day,
+            index: self.lines_read,
+            text: "",
+        }
+        .error_at_end(expected)
+    }
+
+    /// Hex SHA-256 of everything read so far: once the input has run out,
+    /// the same as [`crate::output::input_hash`] of the whole thing.
+    pub fn input_hash(&self) -> String {
+        self.hasher
+            .clone()
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// What streaming made of one part.
+#[derive(Debug)]
+pub enum Streamed<A> {
+    Answer(A),
+    /// Solving the part went wrong, though the input itself was fine.
+    Failed(anyhow::Error),
+    /// The part needs the whole input at once, so it can't be streamed.
+    NeedsWholeInput,
+}
+
+impl<A> Streamed<A> {
+    pub fn map<B>(self, f: impl FnOnce(A) -> B) -> Streamed<B> {
+        match self {
+            Streamed::Answer(answer) => Streamed::Answer(f(answer)),
+            Streamed::Failed(e) => Streamed::Failed(e),
+            Streamed::NeedsWholeInput => Streamed::NeedsWholeInput,
+        }
+    }
+}
+
+impl<A> From<Result<A>> for Streamed<A> {
+    fn from(result: Result<A>) -> Streamed<A> {
+        match result {
+            Ok(answer) => Streamed::Answer(answer),
+            Err(e) => Streamed::Failed(e),
+        }
+    }
+}
+
+/// What streaming made of both parts of a day.
+pub type StreamedParts<A1, A2> = (Streamed<A1>, Streamed<A2>);
+
+/// Everything that came of streaming one day's input.
+pub struct StreamReport {
+    pub parts: [Streamed<String>; 2],
+    pub input_hash: String,
+    /// How long reading and solving took, together, since they happen at the same time.
+    pub elapsed: Duration,
+}
+
+/// Solve `day` straight from `lines`, reading each line only once.
+pub fn solve(day: &dyn Day, mut lines: LineSource) -> Result<StreamReport> {
+    let now = Instant::now();
+    let parts = day.stream(&mut lines)?;
+    let elapsed = now.elapsed();
+
+    // Anything the day didn't read still counts towards the hash.
+    while lines.next_line()?.is_some() {}
+
+    Ok(StreamReport {
+        parts,
+        input_hash: lines.input_hash(),
+        elapsed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{output, parse};
+
+    #[test]
+    fn splits_and_hashes_like_the_whole_input() {
+        for input in [
+            "L68\r\nR5\n\nx\r\r\nlast",
+            "x\r\r",
+            "x\r",
+            "x\r\n\r",
+            "\r\r\n",
+        ] {
+            let mut lines = LineSource::new(1, Cursor::new(input));
+
+            let mut streamed = Vec::new();
+            while let Some(line) = lines.next_line().unwrap() {
+                streamed.push((line.index, line.text.to_string()));
+            }
+            let whole = parse::lines(1, input)
+                .map(|line| (line.index, line.text.to_string()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(streamed, whole, "{:?}", input);
+            assert_eq!(lines.input_hash(), output::input_hash(input));
+        }
+
+        let mut lines = LineSource::new(1, Cursor::new("L68\r\nR5\n\nx\r\r\nlast"));
+        while lines.next_line().unwrap().is_some() {}
+        assert_eq!(lines.error_at_end("more").line, 6);
+    }
+
+    #[test]
+    fn invalid_utf8_is_an_error() {
+        let mut lines = LineSource::new(3, Cursor::new(b"12\n\xff3\n".to_vec()));
+
+        assert!(lines.next_line().is_ok());
+        assert!(
+            lines
+                .next_line()
+                .unwrap_err()
+                .to_string()
+                .contains("line 2")
+        );
+    }
+}
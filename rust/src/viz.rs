@@ -0,0 +1,54 @@
+//! Step-by-step pictures of the days that simulate something, for `aoc viz` to animate.
+//!
+//! Nothing here knows about terminals: a day hands back an [`Animation`], which draws each
+//! step as a grid of characters and says what each one means, and the viewer decides how
+//! that looks on screen.
+
+use crate::grid::{Grid, Point};
+
+/// What a character in a frame stands for, so the viewer can colour it to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    /// Background, like empty floor.
+    Faded,
+    Plain,
+    /// Whatever the last step changed.
+    Active,
+    /// Something worth picking out that isn't changing right now, like the dial's 0.
+    Marked,
+    /// One of any number of groups, like the circuits in day 8.
+    Group(usize),
+}
+
+/// One step of an animation.
+pub struct Frame {
+    pub picture: Grid<(char, Tone)>,
+    /// What just happened, and any running totals.
+    pub caption: String,
+}
+
+impl Frame {
+    /// A blank picture of the given size, to draw onto.
+    pub fn blank(width: usize, height: usize, caption: String) -> Frame {
+        Frame {
+            picture: Grid::new(width, height, (' ', Tone::Faded)),
+            caption,
+        }
+    }
+
+    /// Draw `char` at `point`, if it's in the picture.
+    pub fn put(&mut self, point: Point, char: char, tone: Tone) {
+        if let Some(cell) = self.picture.get_mut(point) {
+            *cell = (char, tone);
+        }
+    }
+}
+
+/// A simulation that can be watched one step at a time.
+pub trait Animation {
+    /// The simulation as it stands.
+    fn frame(&self) -> Frame;
+
+    /// Move the simulation on a step, or return false if it's already finished.
+    fn step(&mut self) -> bool;
+}
@@ -0,0 +1,79 @@
+//! Checks every day against real puzzle inputs, but only when an `answers.toml`
+//! sits next to them in the input directory. See [`aoc::answers`] for its layout;
+//! `aoc run --all --record` will fill it in.
+//!
+//! Without that file there's nothing to check against, so the test passes trivially.
+//! Inputs that aren't already on disk are skipped rather than downloaded.
+
+use aoc::{
+    Part,
+    answers::{self, AnswerDb},
+    input, output,
+};
+
+#[test]
+fn real_inputs_match_recorded_answers() {
+    let Some(answers_path) = input::candidate_dirs()
+        .into_iter()
+        .map(|dir| dir.join(answers::FILE_NAME))
+        .find(|path| path.is_file())
+    else {
+        eprintln!(
+            "No {} found, skipping real input checks",
+            answers::FILE_NAME
+        );
+        return;
+    };
+
+    let db = AnswerDb::load(&answers_path).unwrap();
+    let mut mismatches = Vec::new();
+
+    for day in aoc::DAYS {
+        let Ok(source) = input::locate(day.day(), None) else {
+            continue;
+        };
+        let Ok(input) = source.read() else {
+            continue;
+        };
+        let input_hash = output::input_hash(&input);
+
+        // Only the parts with a recorded answer are checked; the others may not even solve.
+        let recorded: Vec<(Part, String)> = Part::BOTH
+            .into_iter()
+            .filter_map(|part| Some((part, db.expected(day.day(), part, &input_hash)?)))
+            .collect();
+        if recorded.is_empty() {
+            continue;
+        }
+
+        let parsed = match day.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                mismatches.push(format!("day {:02}: {:#}", day.day(), e));
+                continue;
+            }
+        };
+
+        for (part, expected) in recorded {
+            match parsed.solve(part) {
+                Ok(actual) if actual == expected => {}
+                Ok(actual) => mismatches.push(format!(
+                    "day {:02} part {}: expected {}, got {}",
+                    day.day(),
+                    part.number(),
+                    expected,
+                    actual
+                )),
+                Err(e) => mismatches.push(format!(
+                    "day {:02} part {}: expected {}, but it failed: {:#}",
+                    day.day(),
+                    part.number(),
+                    expected,
+                    e
+                )),
+            }
+        }
+    }
+
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
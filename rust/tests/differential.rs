@@ -0,0 +1,266 @@
+//! Checks each clever solution against an obvious brute force on thousands of small random inputs.
+//!
+//! The inputs are generated as puzzle text, in the same format as the real thing. When the two
+//! disagree, proptest shrinks the input down as far as it can while they still disagree, and the
+//! result is written out as an input file that any `dayNN` binary can be pointed at.
+
+use std::{collections::HashSet, fmt::Debug, fs, path::Path};
+
+use aoc::{Solution, day01::Day01, day02::Day02, day03::Day03, day05::Day05};
+use itertools::Itertools;
+use proptest::{
+    prelude::*,
+    test_runner::{Config, TestError, TestRunner},
+};
+
+const CASES: u32 = 2000;
+
+/// Run `clever` and `naive` against inputs from `inputs` until they disagree or we run out of
+/// cases. `name` is used for the counterexample file, e.g. `day01-part2`.
+fn differential<A: PartialEq + Debug>(
+    name: &str,
+    inputs: impl Strategy<Value = String>,
+    clever: impl Fn(&str) -> A,
+    naive: impl Fn(&str) -> A,
+) {
+    let mut runner = TestRunner::new(Config {
+        cases: CASES,
+        failure_persistence: None,
+        ..Config::default()
+    });
+
+    let result = runner.run(&inputs, |input| {
+        prop_assert_eq!(clever(&input), naive(&input));
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, input)) => {
+            let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("differential");
+            let path = dir.join(format!("{}.input.txt", name));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(&path, &input).unwrap();
+
+            panic!(
+                "{}: clever and naive disagree on this input, saved to {}:\n{}\n{}",
+                name,
+                path.display(),
+                input,
+                reason
+            );
+        }
+        Err(e) => panic!("{}: {}", name, e),
+    }
+}
+
+/// Parse `input` as day `S` and run one of its parts, which had better not fail.
+fn solve<S: Solution, A>(input: &str, part: impl Fn(&S::Input) -> anyhow::Result<A>) -> A {
+    part(&S::parse(input).unwrap()).unwrap()
+}
+
+// --- Day 01: dial rotations like "L68" ---
+
+fn day01_input() -> impl Strategy<Value = String> {
+    prop::collection::vec((prop::bool::ANY, 1..=350_i32), 1..20).prop_map(|rotations| {
+        rotations
+            .into_iter()
+            .map(|(left, amt)| format!("{}{}\n", if left { 'L' } else { 'R' }, amt))
+            .collect()
+    })
+}
+
+/// Turn the dial one click at a time, counting every time it points at 0:
+/// after each rotation for part 1, after every click for part 2.
+fn day01_clicks(input: &str) -> (i32, i32) {
+    let mut pos = 50;
+    let (mut at_end, mut passing) = (0, 0);
+
+    for (dir, amt) in Day01::parse(input).unwrap() {
+        for _ in 0..amt {
+            pos = (pos + dir).rem_euclid(100);
+            if pos == 0 {
+                passing += 1;
+            }
+        }
+        if pos == 0 {
+            at_end += 1;
+        }
+    }
+
+    (at_end, passing)
+}
+
+#[test]
+fn day01_part1_matches_click_simulation() {
+    differential(
+        "day01-part1",
+        day01_input(),
+        |input| solve::<Day01, _>(input, Day01::part1),
+        |input| day01_clicks(input).0.into(),
+    );
+}
+
+#[test]
+fn day01_part2_matches_click_simulation() {
+    differential(
+        "day01-part2",
+        day01_input(),
+        |input| solve::<Day01, _>(input, Day01::part2),
+        |input| day01_clicks(input).1.into(),
+    );
+}
+
+// --- Day 02: comma-separated ID ranges like "11-22" ---
+
+fn day02_input() -> impl Strategy<Value = String> {
+    prop::collection::vec((1..200_000_u64, 0..500_u64), 1..5).prop_map(|ranges| {
+        let ranges = ranges
+            .into_iter()
+            .map(|(first, len)| format!("{}-{}", first, first + len))
+            .join(",");
+        format!("{}\n", ranges)
+    })
+}
+
+/// Does `id` consist of some run of digits repeated exactly `repeats` times?
+fn repeats_exactly(id: u64, repeats: usize) -> bool {
+    let digits = id.to_string().into_bytes();
+
+    digits.len() >= repeats
+        && digits.len().is_multiple_of(repeats)
+        && digits.chunks(digits.len() / repeats).all_equal()
+}
+
+/// Walk every ID in every range, summing the ones whose digits repeat in one of `repeat_counts`.
+fn day02_enumerate(input: &str, repeat_counts: impl Fn(usize) -> Vec<usize>) -> u64 {
+    Day02::parse(input)
+        .unwrap()
+        .into_iter()
+        .flat_map(|(first, last)| first..=last)
+        .filter(|&id| {
+            repeat_counts(id.to_string().len())
+                .into_iter()
+                .any(|repeats| repeats_exactly(id, repeats))
+        })
+        .sum()
+}
+
+#[test]
+fn day02_part1_matches_digit_enumeration() {
+    differential(
+        "day02-part1",
+        day02_input(),
+        |input| solve::<Day02, _>(input, Day02::part1),
+        |input| day02_enumerate(input, |_| vec![2]).into(),
+    );
+}
+
+#[test]
+fn day02_part2_matches_digit_enumeration() {
+    differential(
+        "day02-part2",
+        day02_input(),
+        |input| solve::<Day02, _>(input, Day02::part2),
+        |input| day02_enumerate(input, |len| (2..=len).collect()).into(),
+    );
+}
+
+// --- Day 03: banks of battery joltage digits ---
+
+fn day03_input() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::collection::vec(1..=9_u32, 12..=15), 1..5).prop_map(|banks| {
+        banks
+            .into_iter()
+            .map(|bank| format!("{}\n", bank.into_iter().join("")))
+            .collect()
+    })
+}
+
+/// Try every way of picking `count` batteries from each bank and keep the best.
+fn day03_every_choice(input: &str, count: usize) -> u64 {
+    Day03::parse(input)
+        .unwrap()
+        .into_iter()
+        .map(|bank| {
+            bank.into_iter()
+                .combinations(count)
+                .map(|chosen| chosen.into_iter().fold(0, |acc, d| acc * 10 + d as u64))
+                .max()
+                .unwrap()
+        })
+        .sum()
+}
+
+#[test]
+fn day03_part1_matches_every_choice() {
+    differential(
+        "day03-part1",
+        day03_input(),
+        |input| solve::<Day03, _>(input, Day03::part1),
+        |input| day03_every_choice(input, 2).into(),
+    );
+}
+
+#[test]
+fn day03_part2_matches_every_choice() {
+    differential(
+        "day03-part2",
+        day03_input(),
+        |input| solve::<Day03, _>(input, Day03::part2),
+        |input| day03_every_choice(input, 12).into(),
+    );
+}
+
+// --- Day 05: fresh ID ranges, a blank line, then ingredient IDs ---
+
+fn day05_input() -> impl Strategy<Value = String> {
+    let ranges = prop::collection::vec((0..200_u64, 0..30_u64), 1..8);
+    let ingredients = prop::collection::vec(0..250_u64, 0..10);
+
+    (ranges, ingredients).prop_map(|(ranges, ingredients)| {
+        let ranges = ranges
+            .into_iter()
+            .map(|(low, len)| format!("{}-{}\n", low, low + len))
+            .join("");
+        let ingredients = ingredients
+            .into_iter()
+            .map(|id| format!("{}\n", id))
+            .join("");
+        format!("{}\n{}", ranges, ingredients)
+    })
+}
+
+/// Every fresh ID, one by one.
+fn day05_fresh_ids(input: &str) -> HashSet<u64> {
+    let (ranges, _) = Day05::parse(input).unwrap();
+
+    ranges
+        .into_iter()
+        .flat_map(|(low, high)| low..=high)
+        .collect()
+}
+
+#[test]
+fn day05_part1_matches_point_set() {
+    differential(
+        "day05-part1",
+        day05_input(),
+        |input| solve::<Day05, _>(input, Day05::part1),
+        |input| {
+            let fresh = day05_fresh_ids(input);
+            let (_, ingredients) = Day05::parse(input).unwrap();
+            ingredients.iter().filter(|id| fresh.contains(id)).count()
+        },
+    );
+}
+
+#[test]
+fn day05_part2_matches_point_set() {
+    differential(
+        "day05-part2",
+        day05_input(),
+        |input| solve::<Day05, _>(input, Day05::part2),
+        |input| day05_fresh_ids(input).len().into(),
+    );
+}
@@ -0,0 +1,127 @@
+//! Runs the input downloader against a tiny local HTTP server, so none of this needs the network.
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use aoc::fetch::{self, FetchConfig};
+
+const SESSION: &str = "cafef00d";
+
+/// Serves `body` for day 1 to anyone with the right session cookie, 404 for other days,
+/// and 400 for the wrong cookie. Returns the base URL and a count of requests served.
+fn stub_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut cookie = None;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(": ")
+                    && name.eq_ignore_ascii_case("cookie")
+                {
+                    cookie = Some(value.to_string());
+                }
+            }
+
+            let (status, content) = if cookie.as_deref() != Some(&format!("session={}", SESSION)) {
+                ("400 Bad Request", "Puzzle inputs differ by user.")
+            } else if request_line.starts_with("GET /2025/day/1/input ") {
+                ("200 OK", body)
+            } else {
+                ("404 Not Found", "Not found")
+            };
+
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                content.len(),
+                content
+            )
+            .unwrap();
+        }
+    });
+
+    (base_url, requests)
+}
+
+fn config(base_url: String, cache_dir: &tempfile::TempDir) -> FetchConfig {
+    FetchConfig {
+        base_url,
+        session: Some(String::from(SESSION)),
+        cache_dir: cache_dir.path().join("input_files"),
+    }
+}
+
+#[test]
+fn downloads_once_then_uses_the_cache() {
+    let (base_url, requests) = stub_server("L68\nR48\n");
+    let cache = tempfile::tempdir().unwrap();
+    let config = config(base_url, &cache);
+
+    let path = fetch::fetch(&config, 1).unwrap();
+    assert_eq!(path, config.cache_path(1));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "L68\nR48\n");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    fetch::fetch(&config, 1).unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn missing_day_is_an_error_and_caches_nothing() {
+    let (base_url, _) = stub_server("L68\n");
+    let cache = tempfile::tempdir().unwrap();
+    let config = config(base_url, &cache);
+
+    let err = fetch::fetch(&config, 2).unwrap_err();
+    assert!(err.to_string().contains("not found"), "{:#}", err);
+    assert!(!config.cache_path(2).exists());
+}
+
+#[test]
+fn bad_session_is_an_error() {
+    let (base_url, _) = stub_server("L68\n");
+    let cache = tempfile::tempdir().unwrap();
+    let mut config = config(base_url, &cache);
+    config.session = Some(String::from("stale"));
+
+    let err = fetch::fetch(&config, 1).unwrap_err();
+    assert!(err.to_string().contains("session"), "{:#}", err);
+    assert!(!config.cache_path(1).exists());
+}
+
+#[test]
+fn no_session_never_touches_the_server() {
+    let (base_url, requests) = stub_server("L68\n");
+    let cache = tempfile::tempdir().unwrap();
+    let mut config = config(base_url, &cache);
+    config.session = None;
+
+    assert!(fetch::fetch(&config, 1).is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,74 @@
+//! The Python solutions should agree with the Rust ones, and when they don't, the input that
+//! shows it should come back as small as it can be.
+
+use std::{fs, path::Path, process::Command};
+
+use aoc::{
+    Part, generate,
+    parity::{self, PythonSolver},
+};
+
+fn have_python() -> bool {
+    let found = Command::new("python3").arg("--version").output().is_ok();
+    if !found {
+        eprintln!("python3 isn't installed, so there's nothing to check the Rust against");
+    }
+    found
+}
+
+fn python_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../python"))
+}
+
+#[test]
+fn python_agrees_on_generated_inputs() {
+    if !have_python() {
+        return;
+    }
+
+    for day in parity::shared_days(python_dir()) {
+        let mut python = PythonSolver::start("python3", python_dir(), day.day()).unwrap();
+
+        for seed in 0..5 {
+            let input = generate::generate(day.day(), 30, seed).unwrap();
+            let comparison = parity::compare(day, &mut python, &input).unwrap();
+            assert!(
+                comparison.diverging_parts().is_empty(),
+                "day {:02}: {:?}",
+                day.day(),
+                comparison
+            );
+        }
+    }
+}
+
+#[test]
+fn a_broken_python_day_is_caught_and_shrunk() {
+    if !have_python() {
+        return;
+    }
+
+    // Part 2 that forgets the dial can't pass 0 when it starts there.
+    let broken = tempfile::tempdir().unwrap();
+    fs::copy(
+        python_dir().join("common.py"),
+        broken.path().join("common.py"),
+    )
+    .unwrap();
+    let day01 = fs::read_to_string(python_dir().join("day01.py")).unwrap();
+    let day01 = day01.replace("if starting_position != 0 and (", "if (");
+    fs::write(broken.path().join("day01.py"), day01).unwrap();
+
+    let day = aoc::get_day(1).unwrap();
+    let mut python = PythonSolver::start("python3", broken.path(), 1).unwrap();
+    let input = "R50\nL5\nR10\nL20\nR3\n";
+
+    let comparison = parity::compare(day, &mut python, input).unwrap();
+    assert_eq!(comparison.diverging_parts(), vec![Part::Two]);
+
+    let minimal = parity::shrink(input, |candidate| {
+        Ok(parity::compare(day, &mut python, candidate)?.diverging_parts() == vec![Part::Two])
+    })
+    .unwrap();
+    assert_eq!(minimal, "R50\nL5\n");
+}
@@ -0,0 +1,81 @@
+//! Every day has to reject bad input with an error, never a panic. The fuzz targets in `fuzz/`
+//! hunt for such input properly; this runs the cheap, common cases on every `cargo test`:
+//! each seed input from the fuzz corpus cut short at every character, with and without CRLF.
+
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use aoc::Part;
+
+/// The input and every shorter version of it, cut at each character.
+fn truncations(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .char_indices()
+        .map(|(i, _)| &input[..i])
+        .chain([input])
+}
+
+#[test]
+fn truncated_and_crlf_inputs_never_panic() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("corpus");
+    let mut panics = Vec::new();
+
+    for day in aoc::DAYS {
+        let dir = corpus.join(format!("day{:02}", day.day()));
+
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let lf = fs::read_to_string(&path).unwrap();
+            let crlf = lf.replace('\n', "\r\n");
+
+            for input in truncations(&lf).chain(truncations(&crlf)) {
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                    if let Ok(parsed) = day.parse(input) {
+                        for part in Part::BOTH {
+                            let _ = parsed.solve(part);
+                        }
+                    }
+                }));
+
+                if outcome.is_err() {
+                    panics.push(format!("day {:02}: {:?}", day.day(), input));
+                }
+            }
+        }
+    }
+
+    assert!(panics.is_empty(), "Panicked on:\n{}", panics.join("\n"));
+}
+
+#[test]
+fn crlf_gives_the_same_answers() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("corpus");
+
+    for day in aoc::DAYS {
+        let dir = corpus.join(format!("day{:02}", day.day()));
+
+        for entry in fs::read_dir(&dir).unwrap() {
+            let lf = fs::read_to_string(entry.unwrap().path()).unwrap();
+            let crlf = lf.replace('\n', "\r\n");
+            let lf = day.parse(&lf).unwrap();
+            let crlf = day.parse(&crlf).unwrap();
+
+            for part in Part::BOTH {
+                assert_eq!(
+                    lf.solve(part).ok(),
+                    crlf.solve(part).ok(),
+                    "day {:02} part {}",
+                    day.day(),
+                    part.number()
+                );
+            }
+        }
+    }
+}
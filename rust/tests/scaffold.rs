@@ -0,0 +1,67 @@
+//! `aoc new` against a copy of this crate's own manifest and `lib.rs`.
+
+use std::{fs, path::Path};
+
+use aoc::scaffold;
+
+fn copy_of_this_crate() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let here = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    fs::create_dir_all(dir.path().join("src").join("bin")).unwrap();
+    fs::copy(here.join("Cargo.toml"), dir.path().join("Cargo.toml")).unwrap();
+    fs::copy(here.join("src/lib.rs"), dir.path().join("src/lib.rs")).unwrap();
+    fs::write(dir.path().join("src/day03.rs"), "// already solved\n").unwrap();
+
+    dir
+}
+
+#[test]
+fn new_day_is_created_and_registered() {
+    let dir = copy_of_this_crate();
+
+    let created = scaffold::new_day(dir.path(), 24).unwrap();
+    assert_eq!(created.len(), 2);
+
+    let module = fs::read_to_string(dir.path().join("src/day24.rs")).unwrap();
+    assert!(module.contains("pub struct Day24;"));
+    assert!(module.contains("const DAY: u8 = 24;"));
+    assert!(module.contains("fn part1_example()"));
+
+    let bin = fs::read_to_string(dir.path().join("src/bin/day24.rs")).unwrap();
+    assert!(bin.contains("aoc::run(&Day24)"));
+    assert!(bin.contains("aoc::count_allocations!();"));
+
+    let lib_rs = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+    assert!(lib_rs.contains("pub mod day24;\n"));
+    assert!(lib_rs.contains("    &day24::Day24,\n];"));
+
+    let cargo_toml: toml::Table =
+        toml::from_str(&fs::read_to_string(dir.path().join("Cargo.toml")).unwrap()).unwrap();
+    let bins = cargo_toml["bin"].as_array().unwrap();
+    assert_eq!(
+        bins.last().unwrap()["path"].as_str(),
+        Some("src/bin/day24.rs")
+    );
+}
+
+#[test]
+fn existing_days_are_left_alone() {
+    let dir = copy_of_this_crate();
+    let lib_rs = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+
+    scaffold::new_day(dir.path(), 24).unwrap();
+    assert!(scaffold::new_day(dir.path(), 24).is_err());
+    assert!(scaffold::new_day(dir.path(), 3).is_err());
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("src/day03.rs")).unwrap(),
+        "// already solved\n"
+    );
+    assert!(!dir.path().join("src/bin/day03.rs").exists());
+
+    // The second attempt at day 24 mustn't have registered it twice.
+    let after = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+    assert_eq!(after.matches("pub mod day24;").count(), 1);
+    assert_ne!(after, lib_rs);
+}
@@ -0,0 +1,88 @@
+//! Solving from a stream has to give the same answers as reading the whole input in first.
+
+use std::{fs, io::Cursor, path::Path};
+
+use aoc::{
+    Part, generate, output,
+    stream::{self, LineSource, Streamed},
+};
+
+/// The days that can solve at least one part from a stream.
+const STREAMING_DAYS: [u8; 5] = [1, 3, 6, 10, 11];
+
+fn check_same_answers(day: u8, input: &str) {
+    let whole = aoc::get_day(day).unwrap().parse(input);
+    let lines = LineSource::new(day, Cursor::new(input.to_string()));
+    let report = stream::solve(aoc::get_day(day).unwrap(), lines);
+
+    let (whole, report) = match (whole, report) {
+        (Ok(whole), Ok(report)) => (whole, report),
+        (Err(_), Err(_)) => return,
+        (whole, report) => panic!(
+            "day {:02}: parsing gave {:?} but streaming gave {:?}",
+            day,
+            whole.err(),
+            report.err()
+        ),
+    };
+
+    assert_eq!(report.input_hash, output::input_hash(input));
+
+    for (part, streamed) in Part::BOTH.into_iter().zip(report.parts) {
+        let expected = whole.solve(part).ok();
+        match streamed {
+            Streamed::Answer(answer) => assert_eq!(Some(answer), expected, "day {:02}", day),
+            Streamed::Failed(_) => assert_eq!(None, expected, "day {:02}", day),
+            Streamed::NeedsWholeInput => assert!(
+                !STREAMING_DAYS.contains(&day) || (day, part) == (6, Part::Two),
+                "day {:02} part {} should stream",
+                day,
+                part.number()
+            ),
+        }
+    }
+}
+
+#[test]
+fn corpus_inputs_and_truncations_stream_to_the_same_answers() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("corpus");
+
+    for day in aoc::DAYS {
+        let dir = corpus.join(format!("day{:02}", day.day()));
+
+        for entry in fs::read_dir(&dir).unwrap() {
+            let input = fs::read_to_string(entry.unwrap().path()).unwrap();
+
+            // Cut short at every character, to catch the two disagreeing about broken input.
+            // Days that don't stream never look at their input, so they can't disagree.
+            if STREAMING_DAYS.contains(&day.day()) {
+                for (i, _) in input.char_indices() {
+                    check_same_answers(day.day(), &input[..i]);
+                }
+            }
+            check_same_answers(day.day(), &input);
+            check_same_answers(day.day(), &input.replace('\n', "\r\n"));
+        }
+    }
+}
+
+#[test]
+fn generated_inputs_stream_to_the_same_answers() {
+    for day in STREAMING_DAYS {
+        for seed in 0..5 {
+            check_same_answers(day, &generate::generate(day, 20, seed).unwrap());
+        }
+    }
+}
+
+#[test]
+fn bad_input_is_an_error_either_way() {
+    check_same_answers(1, "L10\nX5\n");
+    check_same_answers(3, "98765432111\n9\n");
+    check_same_answers(6, "1 2\n3 4 5\n+ *\n");
+    check_same_answers(6, "1 2\n3 4\n+ *\n5 6\n");
+    check_same_answers(10, "[.#] (0,2) {1,2}\n");
+    check_same_answers(11, "you: aaa\naaa: bbb\n");
+}
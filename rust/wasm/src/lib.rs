@@ -0,0 +1,77 @@
+//! The day solvers for the browser, through wasm-bindgen. Used by the playground page in
+//! www/, which times each step itself: `std::time::Instant` isn't available in the browser.
+//!
+//! ```js
+//! const puzzle = new Puzzle(1, text);   // throws with the parse error if it doesn't parse
+//! puzzle.solve(2);                      // the answer, as a string
+//! ```
+
+use anyhow::{Result, anyhow, bail};
+use aoc::{Parsed, Part};
+use wasm_bindgen::prelude::*;
+
+fn parse(day: u8, input: &str) -> Result<Box<dyn Parsed>> {
+    aoc::get_day(day)
+        .ok_or_else(|| anyhow!("No solution for day {}", day))?
+        .parse(input)
+}
+
+fn part(part: u8) -> Result<Part> {
+    match part {
+        1 => Ok(Part::One),
+        2 => Ok(Part::Two),
+        _ => bail!("There's no part {}, only 1 and 2", part),
+    }
+}
+
+fn js_error(e: anyhow::Error) -> JsError {
+    JsError::new(&format!("{:#}", e))
+}
+
+/// The days there are solutions for, in order.
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    aoc::DAYS.iter().map(|day| day.day()).collect()
+}
+
+/// One day's parsed input, ready to solve either part of.
+#[wasm_bindgen]
+pub struct Puzzle {
+    parsed: Box<dyn Parsed>,
+}
+
+#[wasm_bindgen]
+impl Puzzle {
+    /// Parse `input` as `day`'s puzzle. Throws if there's no such day or the input is bad.
+    #[wasm_bindgen(constructor)]
+    pub fn new(day: u8, input: &str) -> Result<Puzzle, JsError> {
+        Ok(Puzzle {
+            parsed: parse(day, input).map_err(js_error)?,
+        })
+    }
+
+    /// Solve part 1 or 2. Throws if that part can't be solved for this input.
+    pub fn solve(&self, part_number: u8) -> Result<String, JsError> {
+        part(part_number)
+            .and_then(|part| self.parsed.solve(part))
+            .map_err(js_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_solves_without_touching_the_filesystem() {
+        let parsed = parse(1, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n").unwrap();
+        assert_eq!(parsed.solve(part(2).unwrap()).unwrap(), "6");
+
+        assert_eq!(
+            parse(26, "").err().unwrap().to_string(),
+            "No solution for day 26"
+        );
+        assert!(part(3).is_err());
+        assert_eq!(days().first(), Some(&1));
+    }
+}
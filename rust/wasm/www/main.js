@@ -0,0 +1,93 @@
+// Drives the playground page. pkg/ is wasm-bindgen's output; see wasm/Cargo.toml for how to
+// build it.
+
+import init, { days, Puzzle } from './pkg/aoc_wasm.js';
+
+const input = document.getElementById('input');
+const file = document.getElementById('file');
+const day = document.getElementById('day');
+const part = document.getElementById('part');
+const solve = document.getElementById('solve');
+const output = document.getElementById('output');
+
+function show(text, isError = false) {
+  output.textContent = text;
+  output.classList.toggle('error', isError);
+}
+
+function formatTime(ms) {
+  return ms < 1 ? `${(ms * 1000).toFixed(0)} µs` : `${ms.toFixed(2)} ms`;
+}
+
+// A panic reaches JS as a trap, not as one of the errors the solvers throw on purpose.
+function rethrowIfCrash(e) {
+  if (e instanceof WebAssembly.RuntimeError) {
+    throw e;
+  }
+}
+
+// Runs `f` and gives back what it returned and how long it took.
+function timed(f) {
+  const start = performance.now();
+  const result = f();
+  return [result, performance.now() - start];
+}
+
+function run() {
+  const lines = [];
+
+  let puzzle;
+  try {
+    const [parsed, ms] = timed(() => new Puzzle(Number(day.value), input.value));
+    puzzle = parsed;
+    lines.push(`Parsed in ${formatTime(ms)}`);
+  } catch (e) {
+    rethrowIfCrash(e);
+    show(`Couldn't parse the input:\n${e.message}`, true);
+    return;
+  }
+
+  const parts = part.value === 'both' ? [1, 2] : [Number(part.value)];
+  let failed = false;
+  for (const n of parts) {
+    try {
+      const [answer, ms] = timed(() => puzzle.solve(n));
+      lines.push(`Part ${n}: ${answer}  (${formatTime(ms)})`);
+    } catch (e) {
+      rethrowIfCrash(e);
+      lines.push(`Part ${n} failed: ${e.message}`);
+      failed = true;
+    }
+  }
+  puzzle.free();
+
+  show(lines.join('\n'), failed);
+}
+
+file.addEventListener('change', async () => {
+  if (file.files.length > 0) {
+    input.value = await file.files[0].text();
+  }
+});
+
+solve.addEventListener('click', () => {
+  solve.disabled = true;
+  show('Solving…');
+  // Let the page repaint before the solver takes over the thread.
+  setTimeout(() => {
+    try {
+      run();
+    } catch (e) {
+      // The module is unusable after a panic.
+      show(`The solver crashed, which is a bug. Reload the page to try again.\n${e}`, true);
+    }
+    solve.disabled = false;
+  }, 0);
+});
+
+await init();
+for (const n of days()) {
+  day.add(new Option(`Day ${n}`, n));
+}
+solve.textContent = 'Solve';
+solve.disabled = false;